@@ -2,7 +2,6 @@
 //! There are two other versions of this example, one using a simple main loop and the other using Bevy.
 
 use bevy::prelude::*;
-use bevy_auto_plugin;
 use bevy_auto_plugin::prelude::{AutoPlugin, auto_plugin_build_hook, auto_system};
 use immediate_stats::*;
 
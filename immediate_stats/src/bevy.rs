@@ -5,8 +5,21 @@
 mod auto_plugin;
 
 use crate::StatContainer;
+use crate::derived::DerivedStat;
+use crate::easing::Easing;
+#[cfg(feature = "fixed_point")]
+use crate::fixed::{Fixed, FixedModifier, FixedStat};
+use crate::grouped::{GroupPolicy, GroupedStat};
+use crate::layered::{FlatLayer, LayeredStat, MultiplierLayer};
+use crate::map::StatMap;
 use crate::modifier::Modifier;
-use crate::stat::Stat;
+use crate::pool::{Pool, PoolPolicy};
+use crate::snapshot::SnapshotStat;
+use crate::stacked::{StackedStat, StackingRule};
+use crate::stat::{Override, Stat};
+use crate::tracked::TrackedStat;
+use crate::value::Rounding;
+use crate::vector::VectorStat;
 use bevy_app::{App, Plugin, PreUpdate, Update};
 use bevy_ecs::component::Mutable;
 use bevy_ecs::prelude::{Component, Query, ResMut, Resource, Without};
@@ -18,19 +31,68 @@ use std::marker::PhantomData;
 #[cfg(feature = "bevy_auto_plugin")]
 pub use auto_plugin::*;
 
+/// Registers stats, [`Override`] and [`Modifier`] with the Bevy type registry for each value type.
+macro_rules! register_stat_types {
+    ($app:expr, $($ty:ty),*) => {
+        $(
+            $app.register_type::<Stat<$ty>>()
+                .register_type::<Override<$ty>>()
+                .register_type::<DerivedStat<$ty>>()
+                .register_type::<LayeredStat<$ty>>()
+                .register_type::<TrackedStat<$ty>>()
+                .register_type::<VectorStat<$ty, 2>>()
+                .register_type::<VectorStat<$ty, 3>>()
+                .register_type::<GroupedStat<$ty>>()
+                .register_type::<Pool<$ty>>()
+                .register_type::<SnapshotStat<$ty>>()
+                .register_type::<StackedStat<$ty>>()
+                .register_type::<StatMap<String, $ty>>()
+                .register_type::<Modifier<$ty>>();
+        )*
+    };
+}
+
+/// Registers [`FixedStat`] and [`FixedModifier`] with the Bevy type registry for each value type.
+#[cfg(feature = "fixed_point")]
+macro_rules! register_fixed_types {
+    ($app:expr, $($ty:ty),*) => {
+        $app.register_type::<Fixed>();
+        $(
+            $app.register_type::<FixedStat<$ty>>()
+                .register_type::<FixedModifier<$ty>>();
+        )*
+    };
+}
+
 /// Configures [system ordering](StatSystems) and registers types with the Bevy type registry.
 ///
 /// - [`StatSystems::Reset`] runs in `PreUpdate`.
-/// - [`StatSystems::Modify`] runs before [`StatSystems::Read`] in `Update`.
+/// - [`StatSystems::Modify`], [`StatSystems::Derive`], and [`StatSystems::Read`] run in that order in `Update`.
 pub struct ImmediateStatsPlugin;
 
 impl Plugin for ImmediateStatsPlugin {
     fn build(&self, app: &mut App) {
         app.register_type::<PauseStatReset>()
-            .register_type::<Stat>()
-            .register_type::<Modifier>()
-            .configure_sets(Update, StatSystems::Modify.before(StatSystems::Read))
-            .configure_sets(PreUpdate, StatSystems::Reset);
+            .register_type::<FlatLayer>()
+            .register_type::<MultiplierLayer>()
+            .register_type::<GroupPolicy>()
+            .register_type::<StackingRule>()
+            .register_type::<PoolPolicy>()
+            .register_type::<Rounding>()
+            .register_type::<Easing>();
+
+        register_stat_types!(
+            app, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64
+        );
+
+        #[cfg(feature = "fixed_point")]
+        register_fixed_types!(app, i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+        app.configure_sets(
+            Update,
+            (StatSystems::Modify, StatSystems::Derive, StatSystems::Read).chain(),
+        )
+        .configure_sets(PreUpdate, StatSystems::Reset);
     }
 }
 
@@ -42,6 +104,8 @@ pub enum StatSystems {
     Reset,
     /// Systems that apply modifiers to stats.
     Modify,
+    /// Systems that [update derived stats](StatContainer::update_derived) from other stats.
+    Derive,
     /// Systems that read the final value of stats.
     Read,
 }
@@ -57,6 +121,8 @@ pub struct PauseStatReset;
 /// This can be paused on a per-entity basis using the [`PauseStatReset`] component.
 ///
 /// Reset occurs in the [`Reset`](StatSystems::Reset) system set during `PreUpdate`.
+/// [Derived stats](StatContainer::update_derived) are updated in the [`Derive`](StatSystems::Derive)
+/// system set during `Update`, even if reset is paused.
 pub struct ResetComponentPlugin<T: Component<Mutability = Mutable> + StatContainer> {
     _phantom: PhantomData<T>,
 }
@@ -66,6 +132,10 @@ impl<T: Component<Mutability = Mutable> + StatContainer> Plugin for ResetCompone
         app.add_systems(
             PreUpdate,
             reset_component_modifiers::<T>.in_set(StatSystems::Reset),
+        )
+        .add_systems(
+            Update,
+            update_component_derived::<T>.in_set(StatSystems::Derive),
         );
     }
 }
@@ -82,6 +152,17 @@ pub fn reset_component_modifiers<T: Component<Mutability = Mutable> + StatContai
     }
 }
 
+/// Calls [`update_derived`](StatContainer::update_derived) on all `T` components.
+///
+/// Use the [`ResetComponentPlugin`] for recommended configuration.
+pub fn update_component_derived<T: Component<Mutability = Mutable> + StatContainer>(
+    mut query: Query<&mut T>,
+) {
+    for mut stat in &mut query {
+        stat.update_derived();
+    }
+}
+
 impl<T: Component<Mutability = Mutable> + StatContainer> ResetComponentPlugin<T> {
     #[allow(missing_docs)]
     pub fn new() -> Self {
@@ -92,7 +173,7 @@ impl<T: Component<Mutability = Mutable> + StatContainer> ResetComponentPlugin<T>
 impl<T: Component<Mutability = Mutable> + StatContainer> Default for ResetComponentPlugin<T> {
     fn default() -> Self {
         Self {
-            _phantom: PhantomData::default(),
+            _phantom: PhantomData,
         }
     }
 }
@@ -100,6 +181,8 @@ impl<T: Component<Mutability = Mutable> + StatContainer> Default for ResetCompon
 /// Calls [`reset_modifiers`](StatContainer::reset_modifiers) on the `T` resource, if it exists.
 ///
 /// Reset occurs in the [`Reset`](StatSystems::Reset) system set during `PreUpdate`.
+/// [Derived stats](StatContainer::update_derived) are updated in the [`Derive`](StatSystems::Derive)
+/// system set during `Update`.
 pub struct ResetResourcePlugin<T: Resource + StatContainer> {
     _phantom: PhantomData<T>,
 }
@@ -109,6 +192,10 @@ impl<T: Resource + StatContainer> Plugin for ResetResourcePlugin<T> {
         app.add_systems(
             PreUpdate,
             reset_resource_modifiers::<T>.in_set(StatSystems::Reset),
+        )
+        .add_systems(
+            Update,
+            update_resource_derived::<T>.in_set(StatSystems::Derive),
         );
     }
 }
@@ -122,6 +209,15 @@ pub fn reset_resource_modifiers<T: Resource + StatContainer>(res: Option<ResMut<
     }
 }
 
+/// Calls [`update_derived`](StatContainer::update_derived) on the `T` resource, if it exists.
+///
+/// Use the [`ResetResourcePlugin`] for recommended configuration.
+pub fn update_resource_derived<T: Resource + StatContainer>(res: Option<ResMut<T>>) {
+    if let Some(mut res) = res {
+        res.update_derived();
+    }
+}
+
 impl<T: Resource + StatContainer> ResetResourcePlugin<T> {
     #[allow(missing_docs)]
     pub fn new() -> Self {
@@ -132,7 +228,7 @@ impl<T: Resource + StatContainer> ResetResourcePlugin<T> {
 impl<T: Resource + StatContainer> Default for ResetResourcePlugin<T> {
     fn default() -> Self {
         Self {
-            _phantom: PhantomData::default(),
+            _phantom: PhantomData,
         }
     }
 }
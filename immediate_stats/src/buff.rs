@@ -0,0 +1,426 @@
+//! Contains the loader for named buffs, which are defined in data files such as RON or JSON.
+
+use crate::StatContainer;
+use crate::parse::ParseStatError;
+use crate::visit::AnyModifier;
+use serde::{Deserialize, Deserializer};
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
+use std::fmt::{Debug, Display, Formatter};
+use std::marker::PhantomData;
+
+/// The stat names and modifier text of each buff, keyed by buff name.
+type Definitions = BTreeMap<String, BTreeMap<String, String>>;
+
+/// Loads buffs for the stats of a container, which are named by their [path](StatContainer::for_each_stat),
+/// such as `speed` or `movement.speed`.
+///
+/// Buff files map buff names to stat names, and stat names to modifiers
+/// that use the same text format as [`Modifier::from_str`](crate::Modifier#method.from_str), such as `+5 x1.5`.
+/// Stat names and modifiers are checked when the file is loaded,
+/// so a typo is reported immediately instead of when the buff is applied.
+/// ```rust
+/// # use immediate_stats::*;
+/// #[derive(StatContainer, Default)]
+/// struct Character {
+///     speed: Stat,
+///     strength: Stat,
+/// }
+///
+/// let schema = BuffSchema::<Character>::default();
+///
+/// let buffs = schema
+///     .load_str("
+///         haste: { speed: x1.5 }
+///         weakened: { strength: -5 }
+///     ")
+///     .unwrap();
+///
+/// let mut character = Character {
+///     speed: Stat::new(10),
+///     strength: Stat::new(10),
+/// };
+///
+/// buffs["haste"].apply(&mut character);
+/// assert_eq!(character.speed.total(), 15);
+///
+/// assert!(schema.load_str("haste: { sped: x1.5 }").is_err());
+/// ```
+pub struct BuffSchema<C> {
+    container: C,
+}
+
+impl<C: StatContainer + Default> Default for BuffSchema<C> {
+    fn default() -> Self {
+        Self::new(C::default())
+    }
+}
+
+impl<C: StatContainer> BuffSchema<C> {
+    /// Creates a schema from the stats that `container` [visits](StatContainer::visit_stats).
+    ///
+    /// Only the stats visited by this container can be named,
+    /// so for an enum this is the stats of the current variant.
+    pub fn new(container: C) -> Self {
+        Self { container }
+    }
+
+    /// Returns true if the container has a stat with the given path.
+    pub fn contains(&self, name: &str) -> bool {
+        let mut found = false;
+        self.container
+            .for_each_stat(|path, _| found |= path == name);
+        found
+    }
+
+    /// Loads buff definitions from any [`serde`] format.
+    ///
+    /// The data should be a map of buff names, to maps of stat names to modifiers.
+    pub fn load<'de, D: Deserializer<'de>>(
+        &self,
+        deserializer: D,
+    ) -> Result<BuffDefinitions<C>, BuffError> {
+        let definitions = Definitions::deserialize(deserializer)
+            .map_err(|error| BuffError::Format(error.to_string()))?;
+
+        self.resolve(definitions)
+    }
+
+    /// Loads buff definitions from a [RON](https://github.com/ron-rs/ron) string,
+    /// such as `{ "haste": { "speed": "x1.3" } }`.
+    pub fn load_ron(&self, text: &str) -> Result<BuffDefinitions<C>, BuffError> {
+        let mut deserializer = ron::Deserializer::from_str(text)
+            .map_err(|error| BuffError::Format(error.to_string()))?;
+        let buffs = self.load(&mut deserializer)?;
+        deserializer
+            .end()
+            .map_err(|error| BuffError::Format(error.to_string()))?;
+        Ok(buffs)
+    }
+
+    /// Loads buff definitions from a JSON string, such as `{ "haste": { "speed": "x1.3" } }`.
+    pub fn load_json(&self, text: &str) -> Result<BuffDefinitions<C>, BuffError> {
+        let mut deserializer = serde_json::Deserializer::from_str(text);
+        let buffs = self.load(&mut deserializer)?;
+        deserializer
+            .end()
+            .map_err(|error| BuffError::Format(error.to_string()))?;
+        Ok(buffs)
+    }
+
+    /// Loads buff definitions where names and modifiers don't need quotes,
+    /// such as `haste: { speed: x1.3 }`.
+    ///
+    /// Buffs and stats are separated by commas or new lines,
+    /// and the whole file may be wrapped in braces.
+    /// Names and modifiers can still be quoted, such as `"haste": { "speed": "x1.3" }`.
+    pub fn load_str(&self, text: &str) -> Result<BuffDefinitions<C>, BuffError> {
+        let definitions = Cursor::new(text).definitions()?;
+        self.resolve(definitions)
+    }
+
+    /// Parses each modifier using the stat at its path.
+    fn resolve(&self, definitions: Definitions) -> Result<BuffDefinitions<C>, BuffError> {
+        let mut buffs = HashMap::with_capacity(definitions.len());
+
+        for (buff, stats) in definitions {
+            let mut effects = Vec::with_capacity(stats.len());
+            let mut invalid = None;
+
+            self.container.for_each_stat(|path, stat| {
+                let Some(text) = stats.get(path) else {
+                    return;
+                };
+
+                match stat.parse_modifier(text) {
+                    Ok(modifier) => effects.push((path.to_string(), modifier)),
+                    Err(error) => {
+                        invalid.get_or_insert((path.to_string(), error));
+                    }
+                }
+            });
+
+            if let Some((stat, error)) = invalid {
+                return Err(BuffError::InvalidModifier { buff, stat, error });
+            }
+
+            let unknown = stats
+                .into_keys()
+                .find(|stat| !effects.iter().any(|(path, _)| path == stat));
+
+            if let Some(stat) = unknown {
+                return Err(BuffError::UnknownStat { buff, stat });
+            }
+
+            let buff_effects = Buff {
+                effects,
+                container: PhantomData,
+            };
+            buffs.insert(buff, buff_effects);
+        }
+
+        Ok(BuffDefinitions { buffs })
+    }
+}
+
+/// A cursor over the text read by [`BuffSchema::load_str`].
+struct Cursor<'a> {
+    input: &'a str,
+    position: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn new(input: &'a str) -> Self {
+        Self { input, position: 0 }
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.input[self.position..]
+    }
+
+    /// Skips whitespace, along with any commas that separate entries.
+    fn skip_separators(&mut self) {
+        let rest = self.rest();
+        let trimmed = rest.trim_start_matches(|c: char| c.is_whitespace() || c == ',');
+        self.position += rest.len() - trimmed.len();
+    }
+
+    /// Consumes `expected` if it comes next, ignoring leading whitespace.
+    fn eat(&mut self, expected: char) -> bool {
+        let rest = self.rest();
+        self.position += rest.len() - rest.trim_start().len();
+
+        if self.rest().starts_with(expected) {
+            self.position += expected.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), BuffError> {
+        if self.eat(expected) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected `{expected}`")))
+        }
+    }
+
+    fn error(&self, message: &str) -> BuffError {
+        BuffError::Format(format!("{message} at position {}", self.position))
+    }
+
+    /// Consumes a quoted string, or the text up to the first character in `end`.
+    fn text(&mut self, end: &[char]) -> Result<String, BuffError> {
+        if self.eat('"') {
+            let Some(len) = self.rest().find('"') else {
+                return Err(self.error("unterminated string"));
+            };
+            let text = &self.rest()[..len];
+            self.position += len + 1;
+            return Ok(text.to_string());
+        }
+
+        let rest = self.rest();
+        let len = rest.find(end).unwrap_or(rest.len());
+        let text = rest[..len].trim();
+
+        if text.is_empty() {
+            return Err(self.error("expected a name or modifier"));
+        }
+
+        self.position += len;
+        Ok(text.to_string())
+    }
+
+    /// Reads every buff, such as `haste: { speed: x1.3 }`, until the end of the input.
+    fn definitions(&mut self) -> Result<Definitions, BuffError> {
+        let braced = self.eat('{');
+        let mut definitions = Definitions::new();
+
+        loop {
+            self.skip_separators();
+
+            if braced && self.eat('}') {
+                break;
+            }
+
+            if self.rest().is_empty() {
+                if braced {
+                    return Err(self.error("expected `}`"));
+                }
+                break;
+            }
+
+            let buff = self.text(&[':', '{', '}', ',', '\n'])?;
+            self.expect(':')?;
+            self.expect('{')?;
+            definitions.insert(buff, self.stats()?);
+        }
+
+        self.skip_separators();
+
+        if !self.rest().is_empty() {
+            return Err(self.error("expected the end of the input"));
+        }
+
+        Ok(definitions)
+    }
+
+    /// Reads the stats of a buff, after its opening brace.
+    fn stats(&mut self) -> Result<BTreeMap<String, String>, BuffError> {
+        let mut stats = BTreeMap::new();
+
+        loop {
+            self.skip_separators();
+
+            if self.eat('}') {
+                return Ok(stats);
+            }
+
+            let stat = self.text(&[':', '{', '}', ',', '\n'])?;
+            self.expect(':')?;
+            let modifier = self.text(&['}', ',', '\n'])?;
+            stats.insert(stat, modifier);
+        }
+    }
+}
+
+/// A set of modifiers that was loaded by a [`BuffSchema`], keyed by stat path.
+pub struct Buff<C> {
+    effects: Vec<(String, AnyModifier)>,
+    container: PhantomData<fn(&mut C)>,
+}
+
+impl<C> Buff<C> {
+    /// Returns the paths of the stats that this buff modifies.
+    pub fn stats(&self) -> impl Iterator<Item = &str> {
+        self.effects.iter().map(|(stat, _)| stat.as_str())
+    }
+}
+
+impl<C: StatContainer> Buff<C> {
+    /// Applies each modifier to the stat at its path in the container.
+    ///
+    /// Stats that the container no longer has, such as those of another enum variant, are skipped.
+    pub fn apply(&self, container: &mut C) {
+        container.for_each_stat_mut(|path, stat| {
+            for (_, modifier) in self.effects.iter().filter(|(stat, _)| stat == path) {
+                stat.apply_modifier(modifier);
+            }
+        });
+    }
+}
+
+impl<C> Debug for Buff<C> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Buff")
+            .field("stats", &self.stats().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+/// Named buffs that were loaded by a [`BuffSchema`].
+pub struct BuffDefinitions<C> {
+    buffs: HashMap<String, Buff<C>>,
+}
+
+impl<C> BuffDefinitions<C> {
+    /// Returns the buff with the given name.
+    pub fn get(&self, name: &str) -> Option<&Buff<C>> {
+        self.buffs.get(name)
+    }
+
+    /// Returns the names of all buffs, in no particular order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.buffs.keys().map(String::as_str)
+    }
+
+    /// Returns the number of buffs.
+    pub fn len(&self) -> usize {
+        self.buffs.len()
+    }
+
+    /// Returns true if there are no buffs.
+    pub fn is_empty(&self) -> bool {
+        self.buffs.is_empty()
+    }
+}
+
+impl<C: StatContainer> BuffDefinitions<C> {
+    /// Applies the buff with the given name, returning false if there is no such buff.
+    pub fn apply(&self, name: &str, container: &mut C) -> bool {
+        self.get(name).map(|buff| buff.apply(container)).is_some()
+    }
+}
+
+impl<C> std::ops::Index<&str> for BuffDefinitions<C> {
+    type Output = Buff<C>;
+
+    /// Returns the buff with the given name.
+    ///
+    /// # Panics
+    /// Panics if there is no buff with the given name.
+    #[track_caller]
+    fn index(&self, name: &str) -> &Buff<C> {
+        match self.get(name) {
+            Some(buff) => buff,
+            None => panic!("no buff named `{name}`"),
+        }
+    }
+}
+
+impl<C> Debug for BuffDefinitions<C> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_map().entries(&self.buffs).finish()
+    }
+}
+
+/// An error returned when loading buff definitions fails.
+#[derive(PartialEq, Debug, Clone)]
+pub enum BuffError {
+    /// The data could not be read, such as invalid JSON or a modifier that was not a string.
+    Format(String),
+    /// A buff referred to a stat that the [`BuffSchema`]'s container does not have.
+    UnknownStat {
+        /// The name of the buff.
+        buff: String,
+        /// The name of the unknown stat.
+        stat: String,
+    },
+    /// A modifier could not be parsed for the stat's value type.
+    InvalidModifier {
+        /// The name of the buff.
+        buff: String,
+        /// The name of the stat.
+        stat: String,
+        /// Why parsing failed.
+        error: ParseStatError,
+    },
+}
+
+impl Display for BuffError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BuffError::Format(error) => write!(f, "invalid buff definitions: {error}"),
+            BuffError::UnknownStat { buff, stat } => {
+                write!(f, "buff `{buff}` modifies unknown stat `{stat}`")
+            }
+            BuffError::InvalidModifier { buff, stat, error } => {
+                write!(
+                    f,
+                    "buff `{buff}` has an invalid modifier for `{stat}`: {error}"
+                )
+            }
+        }
+    }
+}
+
+impl Error for BuffError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            BuffError::InvalidModifier { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
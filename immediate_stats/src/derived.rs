@@ -0,0 +1,137 @@
+//! Contains a stat whose base is calculated from other stats.
+
+use crate::StatContainer;
+use crate::modifier::Modifier;
+use crate::stat::Stat;
+use crate::value::StatValue;
+use crate::visit::{StatVisitor, StatVisitorMut};
+use std::fmt::{Display, Formatter};
+use std::ops::{AddAssign, DivAssign, MulAssign, SubAssign};
+
+/// A [`Stat`] whose base is calculated from the totals of other stats,
+/// such as attack power being double the strength plus the weapon damage.
+///
+/// The base is set using [`DerivedStat::derive_from`], which is usually called by a
+/// [derived](macro@crate::StatContainer) [`update_derived`][update] implementation:
+/// ```rust
+/// # use immediate_stats::*;
+/// #[derive(StatContainer)]
+/// struct Combat {
+///     strength: Stat,
+///     weapon: Stat,
+///     #[derived(self.strength.total() * 2 + self.weapon.total())]
+///     attack: DerivedStat,
+/// }
+///
+/// let mut combat = Combat {
+///     strength: Stat::new(10),
+///     weapon: Stat::new(5),
+///     attack: DerivedStat::default(),
+/// };
+///
+/// combat.strength += 5;
+/// combat.attack *= 2.0; // Derived stats can still have their own modifiers.
+/// combat.update_derived();
+/// assert_eq!(combat.attack.total(), 70); // ((10 + 5) x 2 + 5) x 2
+/// ```
+///
+/// Like a [`Stat`], modifiers are reset when [`reset_modifiers`][reset] is called.
+/// The base is not reset, but will be outdated until [`update_derived`][update] is called again.
+///
+/// [reset]: StatContainer::reset_modifiers
+/// [update]: StatContainer::update_derived
+#[derive(PartialEq, Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "bevy",
+    derive(bevy_reflect::Reflect),
+    reflect(PartialEq, Debug, Clone)
+)]
+pub struct DerivedStat<T: StatValue = i32> {
+    /// The stat that the derived base and any modifiers are applied to.
+    pub stat: Stat<T>,
+}
+
+impl<T: StatValue> DerivedStat<T> {
+    /// Returns the most recently derived base value.
+    pub fn base(&self) -> T {
+        self.stat.base
+    }
+
+    /// Overwrites the base with a newly derived value, keeping any modifiers.
+    pub fn derive_from(&mut self, base: T) {
+        self.stat.base = base;
+    }
+
+    /// Calculates the total value of the stat. See [`Stat::total`].
+    pub fn total(&self) -> T {
+        self.stat.total()
+    }
+
+    /// Applies the [`Modifier`] values to the inner stat. See [`Stat::apply`].
+    pub fn apply(&mut self, modifier: Modifier<T>) {
+        self.stat.apply(modifier);
+    }
+}
+
+impl<T: StatValue> StatContainer for DerivedStat<T> {
+    fn reset_modifiers(&mut self) {
+        self.stat.reset_modifiers();
+    }
+
+    fn visit_stats(&self, visitor: &mut StatVisitor<'_>) {
+        self.stat.visit_stats(visitor);
+    }
+
+    fn visit_stats_mut(&mut self, visitor: &mut StatVisitorMut<'_>) {
+        self.stat.visit_stats_mut(visitor);
+    }
+}
+
+impl<T: StatValue> Default for DerivedStat<T> {
+    fn default() -> Self {
+        Self::from(Stat::default())
+    }
+}
+
+impl<T: StatValue> From<Stat<T>> for DerivedStat<T> {
+    fn from(stat: Stat<T>) -> Self {
+        Self { stat }
+    }
+}
+
+impl<T: StatValue> AddAssign<T> for DerivedStat<T> {
+    /// Adds to the inner stat's bonus.
+    fn add_assign(&mut self, rhs: T) {
+        self.stat += rhs;
+    }
+}
+
+impl<T: StatValue> SubAssign<T> for DerivedStat<T> {
+    /// Subtracts from the inner stat's bonus.
+    fn sub_assign(&mut self, rhs: T) {
+        self.stat -= rhs;
+    }
+}
+
+impl<T: StatValue> MulAssign<f32> for DerivedStat<T> {
+    /// Multiplies the inner stat's multiplier.
+    #[track_caller]
+    fn mul_assign(&mut self, rhs: f32) {
+        self.stat *= rhs;
+    }
+}
+
+impl<T: StatValue> DivAssign<f32> for DerivedStat<T> {
+    /// Divides the inner stat's multiplier.
+    #[track_caller]
+    fn div_assign(&mut self, rhs: f32) {
+        self.stat /= rhs;
+    }
+}
+
+impl<T: StatValue> Display for DerivedStat<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.stat.fmt(f)
+    }
+}
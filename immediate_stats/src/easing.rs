@@ -0,0 +1,70 @@
+//! Contains easing curves for scaling modifiers.
+
+/// A curve that remaps the fraction used to [scale](crate::Modifier::scaled_with) a modifier,
+/// such as making a buff ramp up slowly before reaching full strength.
+///
+/// Curves should map `0.0` to `0.0` and `1.0` to `1.0`,
+/// so that a modifier has no effect at zero and its full effect at one.
+///
+/// This is implemented for the built-in [`Easing`] functions,
+/// and for any closure that takes and returns an `f32`:
+/// ```rust
+/// # use immediate_stats::*;
+/// let cubic = |t: f32| t * t * t;
+/// let modifier: Modifier = Modifier::new(0, 3.0).scaled_with(0.5, &cubic);
+/// assert_eq!(modifier.multiplier, 1.25);
+/// ```
+pub trait EasingCurve {
+    /// Remaps a fraction, which is usually between zero and one.
+    fn ease(&self, fraction: f32) -> f32;
+}
+
+impl<F: Fn(f32) -> f32> EasingCurve for F {
+    fn ease(&self, fraction: f32) -> f32 {
+        self(fraction)
+    }
+}
+
+/// Common [easing curves](EasingCurve), which all map `0.0` to `0.0` and `1.0` to `1.0`.
+///
+/// See [easings.net](https://easings.net) for visual examples.
+#[derive(PartialEq, Eq, Debug, Default, Copy, Clone, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "bevy",
+    derive(bevy_reflect::Reflect),
+    reflect(PartialEq, Debug, Clone, Hash)
+)]
+pub enum Easing {
+    /// Leaves the fraction unchanged, which is the same as [`Modifier::scaled`](crate::Modifier::scaled).
+    #[default]
+    Linear,
+    /// Starts slowly and speeds up (quadratic).
+    EaseIn,
+    /// Starts quickly and slows down (quadratic).
+    EaseOut,
+    /// Starts and ends slowly, and is fastest in the middle (quadratic).
+    EaseInOut,
+    /// Starts and ends slowly, using the cubic [smoothstep](https://en.wikipedia.org/wiki/Smoothstep) function.
+    SmoothStep,
+}
+
+impl EasingCurve for Easing {
+    fn ease(&self, fraction: f32) -> f32 {
+        let t = fraction;
+
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t,
+            Easing::EaseOut => 1.0 - (1.0 - t) * (1.0 - t),
+            Easing::EaseInOut => {
+                if t < 0.5 {
+                    2.0 * t * t
+                } else {
+                    1.0 - 2.0 * (1.0 - t) * (1.0 - t)
+                }
+            }
+            Easing::SmoothStep => t * t * (3.0 - 2.0 * t),
+        }
+    }
+}
@@ -0,0 +1,50 @@
+//! Contains the error returned by checked stat operations.
+
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+/// An error returned by checked operations, such as [`Stat::checked_total`](crate::Stat::checked_total).
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum StatError {
+    /// Adding two bonuses, or a bonus to the base, overflowed the stat's [bonus type](crate::StatValue::Bonus).
+    BonusOverflow,
+    /// The multiplier was NaN or infinite.
+    InvalidMultiplier(f32),
+    /// The total did not fit in the stat's value type after the multiplier was applied.
+    TotalOverflow,
+    /// A [persistent](crate::Stat::add_persistent) multiplier was zero, NaN or infinite,
+    /// so it could not be removed later.
+    InvalidPersistentMultiplier(f32),
+    /// A [`PersistentHandle`](crate::PersistentHandle) was used with a stat that didn't create it.
+    ForeignHandle,
+}
+
+impl Display for StatError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StatError::BonusOverflow => write!(f, "bonus overflowed the stat's bonus type"),
+            StatError::InvalidMultiplier(multiplier) => {
+                write!(f, "multiplier must be finite, but was {multiplier}")
+            }
+            StatError::TotalOverflow => write!(f, "total overflowed the stat's value type"),
+            StatError::InvalidPersistentMultiplier(multiplier) => {
+                write!(
+                    f,
+                    "persistent multipliers must be finite and non-zero, but was {multiplier}"
+                )
+            }
+            StatError::ForeignHandle => write!(f, "handle was created by a different stat"),
+        }
+    }
+}
+
+impl Error for StatError {}
+
+/// Panics in debug builds if a multiplier is NaN or infinite.
+#[track_caller]
+pub(crate) fn debug_assert_finite(multiplier: f32) {
+    debug_assert!(
+        multiplier.is_finite(),
+        "multiplier must be finite, but was {multiplier}"
+    );
+}
@@ -0,0 +1,450 @@
+//! Contains deterministic fixed-point versions of [`Stat`](crate::Stat) and [`Modifier`](crate::Modifier).
+
+use crate::StatContainer;
+use crate::modifier::Modifier;
+use crate::parse::{ParseStatError, ParseStatErrorKind};
+use crate::value::{Rounding, StatValue};
+use crate::visit::{AnyModifier, AnyStat, StatVisitor, StatVisitorMut};
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign};
+
+/// The number of fractional bits in a [`Fixed`].
+const FRACTION_BITS: u32 = 32;
+
+/// A signed fixed-point number with 32 integer bits and 32 fractional bits.
+///
+/// Unlike `f32`, arithmetic on this type only uses integer operations,
+/// so results are bit-identical across platforms, compilers, and optimization levels.
+/// Operations saturate at the bounds of the type instead of overflowing.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Copy, Clone, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "bevy",
+    derive(bevy_reflect::Reflect),
+    reflect(PartialEq, Debug, Clone, Hash)
+)]
+pub struct Fixed(i64);
+
+impl Fixed {
+    /// Zero.
+    pub const ZERO: Self = Self(0);
+    /// One, which is the identity for multipliers.
+    pub const ONE: Self = Self(1 << FRACTION_BITS);
+
+    /// Creates a fixed-point number from its raw bits.
+    pub const fn from_bits(bits: i64) -> Self {
+        Self(bits)
+    }
+
+    /// Returns the raw bits of the fixed-point number.
+    pub const fn to_bits(self) -> i64 {
+        self.0
+    }
+
+    /// Creates a fixed-point number from an integer.
+    pub const fn from_int(value: i32) -> Self {
+        Self((value as i64) << FRACTION_BITS)
+    }
+
+    /// Creates a fixed-point number from a fraction, such as `3 / 2` for `1.5`.
+    ///
+    /// The result is rounded toward zero.
+    ///
+    /// # Panics
+    /// Panics if the denominator is zero.
+    pub const fn from_ratio(numerator: i64, denominator: i64) -> Self {
+        Self::saturate(((numerator as i128) << FRACTION_BITS) / denominator as i128)
+    }
+
+    /// Creates a fixed-point number from a float, rounding toward zero.
+    ///
+    /// The conversion itself is deterministic, but prefer [`Fixed::from_ratio`]
+    /// when the value is computed rather than written as a literal.
+    pub fn from_f32(value: f32) -> Self {
+        Self((value as f64 * Self::ONE.0 as f64) as i64)
+    }
+
+    /// Converts the fixed-point number into a float. This is intended for display purposes.
+    pub fn to_f32(self) -> f32 {
+        (self.0 as f64 / Self::ONE.0 as f64) as f32
+    }
+
+    /// Multiplies an integer by the fixed-point number, using the given rounding strategy.
+    pub fn scale(self, value: i128, rounding: Rounding) -> i128 {
+        let product = value.saturating_mul(self.0 as i128);
+        let floor = product >> FRACTION_BITS;
+        let remainder = product - (floor << FRACTION_BITS);
+        let half = 1i128 << (FRACTION_BITS - 1);
+
+        if remainder == 0 {
+            return floor;
+        }
+
+        match rounding {
+            Rounding::Floor => floor,
+            Rounding::Ceil => floor + 1,
+            Rounding::Truncate if product < 0 => floor + 1,
+            Rounding::Truncate => floor,
+            Rounding::HalfAway if remainder == half && product < 0 => floor,
+            Rounding::HalfEven if remainder == half => floor + (floor & 1),
+            Rounding::HalfAway | Rounding::HalfEven if remainder >= half => floor + 1,
+            Rounding::HalfAway | Rounding::HalfEven => floor,
+        }
+    }
+
+    /// Linearly interpolates between one and this value.
+    ///
+    /// When the fraction is zero the result is one, and when it is one the result is this value.
+    pub fn lerp_from_one(self, fraction: Fixed) -> Self {
+        Self::ONE + (self - Self::ONE) * fraction
+    }
+
+    /// Clamps a wide value into the bounds of the type.
+    const fn saturate(bits: i128) -> Self {
+        if bits > i64::MAX as i128 {
+            Self(i64::MAX)
+        } else if bits < i64::MIN as i128 {
+            Self(i64::MIN)
+        } else {
+            Self(bits as i64)
+        }
+    }
+}
+
+impl Add for Fixed {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self(self.0.saturating_add(rhs.0))
+    }
+}
+
+impl Sub for Fixed {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self(self.0.saturating_sub(rhs.0))
+    }
+}
+
+impl Mul for Fixed {
+    type Output = Self;
+
+    /// Multiplies two fixed-point numbers, rounding toward negative infinity.
+    fn mul(self, rhs: Self) -> Self {
+        Self::saturate((self.0 as i128 * rhs.0 as i128) >> FRACTION_BITS)
+    }
+}
+
+impl Div for Fixed {
+    type Output = Self;
+
+    /// Divides two fixed-point numbers, rounding toward zero.
+    ///
+    /// # Panics
+    /// Panics if `rhs` is zero.
+    fn div(self, rhs: Self) -> Self {
+        Self::saturate(((self.0 as i128) << FRACTION_BITS) / rhs.0 as i128)
+    }
+}
+
+impl From<i32> for Fixed {
+    fn from(value: i32) -> Self {
+        Self::from_int(value)
+    }
+}
+
+impl Display for Fixed {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if let Some(precision) = f.precision() {
+            write!(f, "{:.*}", precision, self.to_f32())
+        } else {
+            write!(f, "{}", self.to_f32())
+        }
+    }
+}
+
+/// An integer [`StatValue`] that can be used with [`FixedStat`].
+pub trait FixedValue: StatValue {
+    /// Converts the value into an `i128` without loss.
+    fn to_i128(self) -> i128;
+
+    /// Converts an `i128` back, saturating at the bounds of the type.
+    fn from_i128(value: i128) -> Self;
+
+    /// Converts a [bonus](StatValue::Bonus) into an `i128` without loss.
+    fn bonus_to_i128(bonus: Self::Bonus) -> i128;
+
+    /// Converts an `i128` into a [bonus](StatValue::Bonus), saturating at the bounds of the bonus type.
+    fn bonus_from_i128(value: i128) -> Self::Bonus;
+}
+
+/// Implements [`FixedValue`] for integers that fit in an `i128`.
+macro_rules! impl_fixed_value {
+    ($($ty:ty),*) => {
+        $(
+            impl FixedValue for $ty {
+                fn to_i128(self) -> i128 {
+                    self as i128
+                }
+
+                fn from_i128(value: i128) -> Self {
+                    value.clamp(<$ty>::MIN as i128, <$ty>::MAX as i128) as $ty
+                }
+
+                fn bonus_to_i128(bonus: Self::Bonus) -> i128 {
+                    bonus as i128
+                }
+
+                fn bonus_from_i128(value: i128) -> Self::Bonus {
+                    type Bonus = <$ty as StatValue>::Bonus;
+                    value.clamp(Bonus::MIN as i128, Bonus::MAX as i128) as Bonus
+                }
+            }
+        )*
+    };
+}
+
+impl_fixed_value!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+/// A [`Modifier`](crate::Modifier) with a deterministic [`Fixed`] multiplier,
+/// that can be [applied](FixedStat::apply) to a [`FixedStat`].
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "bevy",
+    derive(bevy_reflect::Reflect),
+    reflect(PartialEq, Debug, Clone)
+)]
+pub struct FixedModifier<T: FixedValue = i32> {
+    /// Added to the `base` of a [`FixedStat`] during calculation.
+    ///
+    /// This is [signed](StatValue::Bonus), so unsigned stats can be debuffed.
+    pub bonus: T::Bonus,
+    /// Multiplies the `base` of a [`FixedStat`] during calculation.
+    pub multiplier: Fixed,
+}
+
+impl<T: FixedValue> FixedModifier<T> {
+    /// Creates a new modifier from a bonus and a multiplier.
+    pub fn new(bonus: T::Bonus, multiplier: Fixed) -> Self {
+        Self { bonus, multiplier }
+    }
+
+    /// Creates a new modifier from a bonus.
+    pub fn from_bonus(bonus: T::Bonus) -> Self {
+        Self {
+            bonus,
+            ..Self::default()
+        }
+    }
+
+    /// Creates a new modifier from a multiplier.
+    pub fn from_multiplier(multiplier: Fixed) -> Self {
+        Self {
+            multiplier,
+            ..Self::default()
+        }
+    }
+
+    /// Returns a new modifier scaled by a fraction.
+    ///
+    /// When the scale is zero, the bonus will be zero while the multiplier will be one.
+    /// The bonus is rounded toward zero.
+    pub fn scaled(&self, fraction: Fixed) -> Self {
+        Self {
+            bonus: T::bonus_from_i128(
+                fraction.scale(T::bonus_to_i128(self.bonus), Rounding::Truncate),
+            ),
+            multiplier: self.multiplier.lerp_from_one(fraction),
+        }
+    }
+}
+
+impl<T: FixedValue> Default for FixedModifier<T> {
+    fn default() -> Self {
+        Self {
+            bonus: T::Bonus::default(),
+            multiplier: Fixed::ONE,
+        }
+    }
+}
+
+impl<T: FixedValue> Display for FixedModifier<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({:+}) x ", self.bonus)?;
+        self.multiplier.fmt(f)
+    }
+}
+
+/// A [`Stat`](crate::Stat) with a deterministic [`Fixed`] multiplier.
+///
+/// This is intended for lockstep and rollback games,
+/// where [`FixedStat::total`] must be bit-identical on every machine.
+/// Requires the `fixed_point` feature flag.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "bevy",
+    derive(bevy_reflect::Reflect),
+    reflect(PartialEq, Debug, Clone)
+)]
+pub struct FixedStat<T: FixedValue = i32> {
+    /// The persistent value of the stat.
+    /// After being [reset](StatContainer::reset_modifiers), [`FixedStat::total`] will be equal to `base`.
+    pub base: T,
+    /// Added to `base` during calculation and gets [reset](StatContainer::reset_modifiers) to zero.
+    ///
+    /// This is [signed](StatValue::Bonus), so unsigned stats can be debuffed.
+    pub bonus: T::Bonus,
+    /// Multiplies the `base` during calculation and gets reset to one every iteration.
+    pub multiplier: Fixed,
+    /// How [`FixedStat::total`] is rounded after the multiplier is applied. This does **not** get reset.
+    pub rounding: Rounding,
+}
+
+impl<T: FixedValue> FixedStat<T> {
+    /// Creates a new stat from a base value.
+    pub fn new(base: T) -> Self {
+        Self {
+            base,
+            ..Default::default()
+        }
+    }
+
+    /// Calculates the total value of the stat, saturating at the bounds of the value type.
+    pub fn total(&self) -> T {
+        let sum = self.base.to_i128() + T::bonus_to_i128(self.bonus);
+        T::from_i128(self.multiplier.scale(sum, self.rounding))
+    }
+
+    /// A builder that overwrites the current bonus with a new value.
+    pub fn with_bonus(mut self, bonus: T::Bonus) -> Self {
+        self.bonus = bonus;
+        self
+    }
+
+    /// A builder that overwrites the multiplier with a new value.
+    pub fn with_multiplier(mut self, multiplier: Fixed) -> Self {
+        self.multiplier = multiplier;
+        self
+    }
+
+    /// A builder that overwrites the rounding strategy of the total.
+    pub fn with_rounding(mut self, rounding: Rounding) -> Self {
+        self.rounding = rounding;
+        self
+    }
+
+    /// Applies the [`FixedModifier`] values to the bonus and multiplier.
+    ///
+    /// This adds the bonuses, and multiplies the multipliers.
+    pub fn apply(&mut self, modifier: FixedModifier<T>) {
+        self.bonus += modifier.bonus;
+        self.multiplier = self.multiplier * modifier.multiplier;
+    }
+
+    /// [Scales](FixedModifier::scaled) and applies the [`FixedModifier`] values to the bonus and multiplier.
+    ///
+    /// When the scale is zero, the bonus will be zero while the multiplier will be one.
+    pub fn apply_scaled(&mut self, modifier: FixedModifier<T>, fraction: Fixed) {
+        self.apply(modifier.scaled(fraction));
+    }
+}
+
+impl<T: FixedValue> StatContainer for FixedStat<T> {
+    fn reset_modifiers(&mut self) {
+        self.bonus = T::Bonus::default();
+        self.multiplier = Fixed::ONE;
+    }
+
+    fn visit_stats(&self, visitor: &mut StatVisitor<'_>) {
+        visitor.visit(self);
+    }
+
+    fn visit_stats_mut(&mut self, visitor: &mut StatVisitorMut<'_>) {
+        visitor.visit(self);
+    }
+}
+
+impl<T: FixedValue> AnyStat for FixedStat<T> {
+    /// Multiplies the stat's multiplier, after converting it using [`Fixed::from_f32`].
+    fn multiply(&mut self, multiplier: f32) {
+        *self *= Fixed::from_f32(multiplier);
+    }
+
+    /// Parses a [`FixedModifier`], converting the multiplier using [`Fixed::from_f32`].
+    ///
+    /// Percentages of the base are not supported, since they aren't part of a [`FixedModifier`].
+    fn parse_modifier(&self, text: &str) -> Result<AnyModifier, ParseStatError> {
+        let modifier: Modifier<T> = text.parse()?;
+
+        if modifier.base_percent != 0.0 {
+            return Err(ParseStatError::new(
+                0,
+                ParseStatErrorKind::Unsupported("a percentage"),
+            ));
+        }
+
+        Ok(AnyModifier::new(FixedModifier::<T>::new(
+            modifier.bonus,
+            Fixed::from_f32(modifier.multiplier),
+        )))
+    }
+
+    fn apply_modifier(&mut self, modifier: &AnyModifier) -> bool {
+        modifier
+            .downcast_ref::<FixedModifier<T>>()
+            .map(|modifier| self.apply(*modifier))
+            .is_some()
+    }
+}
+
+impl<T: FixedValue> Default for FixedStat<T> {
+    fn default() -> Self {
+        Self {
+            base: T::default(),
+            bonus: T::Bonus::default(),
+            multiplier: Fixed::ONE,
+            rounding: Rounding::default(),
+        }
+    }
+}
+
+impl<T: FixedValue> AddAssign<T> for FixedStat<T> {
+    /// Adds to the stat's bonus.
+    fn add_assign(&mut self, rhs: T) {
+        self.bonus += rhs.to_bonus();
+    }
+}
+
+impl<T: FixedValue> SubAssign<T> for FixedStat<T> {
+    /// Subtracts from the stat's bonus.
+    fn sub_assign(&mut self, rhs: T) {
+        self.bonus -= rhs.to_bonus();
+    }
+}
+
+impl<T: FixedValue> MulAssign<Fixed> for FixedStat<T> {
+    /// Multiplies the stat's multiplier.
+    fn mul_assign(&mut self, rhs: Fixed) {
+        self.multiplier = self.multiplier * rhs;
+    }
+}
+
+impl<T: FixedValue> DivAssign<Fixed> for FixedStat<T> {
+    /// Divides the stat's multiplier.
+    ///
+    /// # Panics
+    /// Panics if `rhs` is zero.
+    fn div_assign(&mut self, rhs: Fixed) {
+        self.multiplier = self.multiplier / rhs;
+    }
+}
+
+impl<T: FixedValue> Display for FixedStat<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({} + {}) x ", self.base, self.bonus)?;
+        self.multiplier.fmt(f)
+    }
+}
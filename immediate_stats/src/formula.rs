@@ -0,0 +1,524 @@
+//! Contains a small expression language for calculating modifiers from named variables.
+
+use crate::modifier::Modifier;
+use crate::value::{Rounding, StatValue};
+use std::borrow::Borrow;
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::hash::{BuildHasher, Hash};
+use std::str::FromStr;
+
+/// How deeply operators and parentheses can be nested,
+/// which stops untrusted formulas from overflowing the stack.
+const MAX_DEPTH: usize = 256;
+
+/// Named values that can be used by a [`Formula`], such as `level` or `agility`.
+///
+/// This is implemented for maps from names to `f64`, and for closures:
+/// ```rust
+/// # use immediate_stats::*;
+/// let formula: Formula = "2 * level + 3".parse().unwrap();
+/// let level = |name: &str| (name == "level").then_some(5.0);
+/// assert_eq!(formula.evaluate(&level), Ok(13.0));
+/// ```
+pub trait Variables {
+    /// Returns the value of a variable, or `None` if it is not defined.
+    fn get(&self, name: &str) -> Option<f64>;
+}
+
+impl<F: Fn(&str) -> Option<f64>> Variables for F {
+    fn get(&self, name: &str) -> Option<f64> {
+        self(name)
+    }
+}
+
+impl<K: Borrow<str> + Hash + Eq, S: BuildHasher> Variables for HashMap<K, f64, S> {
+    fn get(&self, name: &str) -> Option<f64> {
+        HashMap::get(self, name).copied()
+    }
+}
+
+impl<K: Borrow<str> + Ord> Variables for BTreeMap<K, f64> {
+    fn get(&self, name: &str) -> Option<f64> {
+        BTreeMap::get(self, name).copied()
+    }
+}
+
+impl<const N: usize> Variables for [(&str, f64); N] {
+    fn get(&self, name: &str) -> Option<f64> {
+        self.iter()
+            .find(|(variable, _)| *variable == name)
+            .map(|(_, value)| *value)
+    }
+}
+
+/// An error returned when parsing or evaluating a [`Formula`] fails.
+#[derive(PartialEq, Debug, Clone)]
+pub enum FormulaError {
+    /// Something else was found where a token was expected, or the formula ended early.
+    Expected {
+        /// The byte offset in the formula.
+        position: usize,
+        /// A description of what was expected, such as `` `)` `` or `a number`.
+        expected: &'static str,
+        /// The character that was found instead, or `None` at the end of the formula.
+        found: Option<char>,
+    },
+    /// A number could not be parsed.
+    InvalidNumber {
+        /// The byte offset in the formula.
+        position: usize,
+        /// The text of the number.
+        number: String,
+    },
+    /// The formula was nested too deeply, such as by using too many operators or parentheses.
+    TooDeep {
+        /// The byte offset in the formula.
+        position: usize,
+    },
+    /// A [`ModifierFormula`] assigned to something other than
+    /// `bonus`, `base_percent` or `multiplier`, or assigned to one more than once.
+    InvalidTarget {
+        /// The byte offset in the formula.
+        position: usize,
+        /// The name that was assigned to.
+        name: String,
+    },
+    /// A variable was used that was not defined.
+    UndefinedVariable(String),
+    /// A value was divided by zero.
+    DivisionByZero,
+    /// The result was not finite, or did not fit in the stat's value type.
+    OutOfRange(f64),
+}
+
+impl Display for FormulaError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FormulaError::Expected {
+                position,
+                expected,
+                found: Some(found),
+            } => write!(
+                f,
+                "expected {expected}, but found `{found}` at position {position}"
+            ),
+            FormulaError::Expected {
+                position,
+                expected,
+                found: None,
+            } => write!(
+                f,
+                "expected {expected}, but found the end of the formula at position {position}"
+            ),
+            FormulaError::InvalidNumber { position, number } => {
+                write!(f, "`{number}` is not a valid number at position {position}")
+            }
+            FormulaError::TooDeep { position } => {
+                write!(f, "formula is nested too deeply at position {position}")
+            }
+            FormulaError::InvalidTarget { position, name } => write!(
+                f,
+                "`{name}` at position {position} must be `bonus`, `base_percent` or `multiplier`, and can only be assigned once"
+            ),
+            FormulaError::UndefinedVariable(name) => write!(f, "variable `{name}` is not defined"),
+            FormulaError::DivisionByZero => write!(f, "division by zero"),
+            FormulaError::OutOfRange(value) => {
+                write!(f, "result {value} does not fit in the stat's value type")
+            }
+        }
+    }
+}
+
+impl Error for FormulaError {}
+
+/// A parsed expression.
+#[derive(PartialEq, Debug, Clone)]
+enum Expr {
+    Number(f64),
+    Variable(String),
+    Negate(Box<Expr>),
+    Binary(Box<Expr>, Operator, Box<Expr>),
+}
+
+#[derive(PartialEq, Debug, Copy, Clone)]
+enum Operator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+}
+
+impl Expr {
+    fn evaluate(&self, variables: &impl Variables) -> Result<f64, FormulaError> {
+        match self {
+            Expr::Number(value) => Ok(*value),
+            Expr::Variable(name) => variables
+                .get(name)
+                .ok_or_else(|| FormulaError::UndefinedVariable(name.clone())),
+            Expr::Negate(expr) => Ok(-expr.evaluate(variables)?),
+            Expr::Binary(lhs, operator, rhs) => {
+                let lhs = lhs.evaluate(variables)?;
+                let rhs = rhs.evaluate(variables)?;
+
+                match operator {
+                    Operator::Add => Ok(lhs + rhs),
+                    Operator::Subtract => Ok(lhs - rhs),
+                    Operator::Multiply => Ok(lhs * rhs),
+                    Operator::Divide if rhs == 0.0 => Err(FormulaError::DivisionByZero),
+                    Operator::Divide => Ok(lhs / rhs),
+                }
+            }
+        }
+    }
+}
+
+/// An arithmetic expression, such as `2 * level + 3`, that is evaluated using named [`Variables`].
+///
+/// Formulas support numbers, variables, parentheses, negation, and the `+ - * /` operators.
+/// Variable names may contain letters, digits, underscores and dots, such as `movement.speed`.
+///
+/// Formulas are sandboxed: they can only read the variables they are given,
+/// and evaluation always finishes.
+/// ```rust
+/// # use immediate_stats::*;
+/// let formula: Formula = "1 + 0.01 * agility".parse().unwrap();
+/// assert_eq!(formula.evaluate(&[("agility", 50.0)]), Ok(1.5));
+///
+/// assert_eq!(
+///     formula.evaluate(&[("level", 5.0)]),
+///     Err(FormulaError::UndefinedVariable("agility".into()))
+/// );
+/// ```
+#[derive(PartialEq, Debug, Clone)]
+pub struct Formula {
+    source: String,
+    expr: Expr,
+}
+
+impl Formula {
+    /// Calculates the value of the formula.
+    ///
+    /// Returns an error if a variable is not defined, or if a value is divided by zero.
+    pub fn evaluate(&self, variables: &impl Variables) -> Result<f64, FormulaError> {
+        self.expr.evaluate(variables)
+    }
+
+    /// Returns the text that the formula was parsed from.
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+}
+
+impl FromStr for Formula {
+    type Err = FormulaError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser::new(s);
+        let expr = parser.expr(0)?;
+        parser.end()?;
+
+        Ok(Self {
+            source: s.trim().to_string(),
+            expr,
+        })
+    }
+}
+
+impl Display for Formula {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.source)
+    }
+}
+
+/// Formulas that calculate each part of a [`Modifier`], such as a bonus that scales with level.
+///
+/// This is parsed from assignments to `bonus`, `base_percent` and `multiplier`,
+/// separated by semicolons or new lines. Parts that are not assigned keep their default value.
+/// ```rust
+/// # use immediate_stats::*;
+/// let formula: ModifierFormula = "bonus = 2 * level + 3; multiplier = 1 + 0.01 * agility"
+///     .parse()
+///     .unwrap();
+///
+/// let mut strength = Stat::new(10);
+/// strength
+///     .apply_formula(&formula, &[("level", 5.0), ("agility", 50.0)])
+///     .unwrap();
+///
+/// assert_eq!(strength.total(), 34); // (10 + 13) * 1.5 = 34.5
+/// ```
+#[derive(PartialEq, Debug, Clone, Default)]
+pub struct ModifierFormula {
+    /// Calculates [`Modifier::bonus`].
+    pub bonus: Option<Formula>,
+    /// Calculates [`Modifier::base_percent`].
+    pub base_percent: Option<Formula>,
+    /// Calculates [`Modifier::multiplier`].
+    pub multiplier: Option<Formula>,
+}
+
+impl ModifierFormula {
+    /// Calculates a modifier, rounding the bonus toward zero.
+    pub fn evaluate<T: StatValue>(
+        &self,
+        variables: &impl Variables,
+    ) -> Result<Modifier<T>, FormulaError> {
+        self.evaluate_rounded(variables, Rounding::Truncate)
+    }
+
+    /// Calculates a modifier, using the given rounding strategy for the bonus.
+    ///
+    /// Returns an error if a variable is not defined, if a value is divided by zero,
+    /// or if the bonus does not fit in the stat's [bonus type](StatValue::Bonus).
+    pub fn evaluate_rounded<T: StatValue>(
+        &self,
+        variables: &impl Variables,
+        rounding: Rounding,
+    ) -> Result<Modifier<T>, FormulaError> {
+        let mut modifier = Modifier::default();
+
+        if let Some(bonus) = &self.bonus {
+            let value = bonus.evaluate(variables)?;
+            modifier.bonus =
+                T::Bonus::checked_from_exact(T::Bonus::exact_from_f64(value), rounding)
+                    .ok_or(FormulaError::OutOfRange(value))?;
+        }
+
+        if let Some(base_percent) = &self.base_percent {
+            modifier.base_percent = finite(base_percent.evaluate(variables)?)?;
+        }
+
+        if let Some(multiplier) = &self.multiplier {
+            modifier.multiplier = finite(multiplier.evaluate(variables)?)?;
+        }
+
+        Ok(modifier)
+    }
+}
+
+/// Converts a result to an `f32`, returning an error if it is not finite.
+fn finite(value: f64) -> Result<f32, FormulaError> {
+    let value32 = value as f32;
+    if value32.is_finite() {
+        Ok(value32)
+    } else {
+        Err(FormulaError::OutOfRange(value))
+    }
+}
+
+impl FromStr for ModifierFormula {
+    type Err = FormulaError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut formula = ModifierFormula::default();
+        let mut offset = 0;
+
+        for line in s.split([';', '\n']) {
+            let start = offset;
+            offset += line.len() + 1;
+
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let Some((target, expr)) = line.split_once('=') else {
+                let mut parser = Parser::new(line);
+                parser.identifier();
+                return Err(parser.expected("`=`").offset(start));
+            };
+
+            let name = target.trim();
+            let invalid_target = || FormulaError::InvalidTarget {
+                position: start + target.len() - target.trim_start().len(),
+                name: name.to_string(),
+            };
+
+            let slot = match name {
+                "bonus" => &mut formula.bonus,
+                "base_percent" => &mut formula.base_percent,
+                "multiplier" => &mut formula.multiplier,
+                _ => return Err(invalid_target()),
+            };
+
+            if slot.is_some() {
+                return Err(invalid_target());
+            }
+
+            let expr_start = start + target.len() + 1;
+            *slot = Some(
+                expr.parse::<Formula>()
+                    .map_err(|error| error.offset(expr_start))?,
+            );
+        }
+
+        Ok(formula)
+    }
+}
+
+impl FormulaError {
+    /// Moves the position of a syntax error, for formulas that are part of a larger string.
+    fn offset(self, offset: usize) -> Self {
+        match self {
+            FormulaError::Expected {
+                position,
+                expected,
+                found,
+            } => FormulaError::Expected {
+                position: position + offset,
+                expected,
+                found,
+            },
+            FormulaError::InvalidNumber { position, number } => FormulaError::InvalidNumber {
+                position: position + offset,
+                number,
+            },
+            FormulaError::TooDeep { position } => FormulaError::TooDeep {
+                position: position + offset,
+            },
+            FormulaError::InvalidTarget { position, name } => FormulaError::InvalidTarget {
+                position: position + offset,
+                name,
+            },
+            error => error,
+        }
+    }
+}
+
+/// A recursive descent parser, where `*` and `/` bind tighter than `+` and `-`.
+struct Parser<'a> {
+    input: &'a str,
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(input: &'a str) -> Self {
+        Self { input, position: 0 }
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.input[self.position..]
+    }
+
+    /// Skips whitespace and returns the next character, without consuming it.
+    fn peek(&mut self) -> Option<char> {
+        let rest = self.rest();
+        self.position += rest.len() - rest.trim_start().len();
+        self.rest().chars().next()
+    }
+
+    fn expected(&mut self, expected: &'static str) -> FormulaError {
+        FormulaError::Expected {
+            found: self.peek(),
+            position: self.position,
+            expected,
+        }
+    }
+
+    fn end(&mut self) -> Result<(), FormulaError> {
+        match self.peek() {
+            None => Ok(()),
+            Some(_) => Err(self.expected("an operator")),
+        }
+    }
+
+    /// Consumes the longest run of characters that match the predicate.
+    fn take_while(&mut self, predicate: impl Fn(char) -> bool) -> &'a str {
+        let rest = self.rest();
+        let len = rest.find(|c| !predicate(c)).unwrap_or(rest.len());
+        self.position += len;
+        &rest[..len]
+    }
+
+    fn identifier(&mut self) -> &'a str {
+        self.peek();
+        self.take_while(|c| c.is_alphanumeric() || c == '_' || c == '.')
+    }
+
+    /// Increases the nesting depth, returning an error if it is too deep.
+    fn deeper(&self, depth: usize) -> Result<usize, FormulaError> {
+        if depth < MAX_DEPTH {
+            Ok(depth + 1)
+        } else {
+            Err(FormulaError::TooDeep {
+                position: self.position,
+            })
+        }
+    }
+
+    /// Parses a sum of terms, such as `a + b - c`.
+    fn expr(&mut self, mut depth: usize) -> Result<Expr, FormulaError> {
+        let mut expr = self.term(depth)?;
+
+        loop {
+            let operator = match self.peek() {
+                Some('+') => Operator::Add,
+                Some('-') => Operator::Subtract,
+                _ => return Ok(expr),
+            };
+
+            // Each operator nests the previous expression one level deeper.
+            depth = self.deeper(depth)?;
+            self.position += 1;
+            expr = Expr::Binary(Box::new(expr), operator, Box::new(self.term(depth)?));
+        }
+    }
+
+    /// Parses a product of factors, such as `a * b / c`.
+    fn term(&mut self, mut depth: usize) -> Result<Expr, FormulaError> {
+        let mut expr = self.factor(depth)?;
+
+        loop {
+            let operator = match self.peek() {
+                Some('*') => Operator::Multiply,
+                Some('/') => Operator::Divide,
+                _ => return Ok(expr),
+            };
+
+            // Each operator nests the previous expression one level deeper.
+            depth = self.deeper(depth)?;
+            self.position += 1;
+            expr = Expr::Binary(Box::new(expr), operator, Box::new(self.factor(depth)?));
+        }
+    }
+
+    /// Parses a number, variable, negation or parenthesized expression.
+    fn factor(&mut self, depth: usize) -> Result<Expr, FormulaError> {
+        match self.peek() {
+            Some('-') => {
+                let depth = self.deeper(depth)?;
+                self.position += 1;
+                Ok(Expr::Negate(Box::new(self.factor(depth)?)))
+            }
+            Some('(') => {
+                let depth = self.deeper(depth)?;
+                self.position += 1;
+                let expr = self.expr(depth)?;
+
+                if self.peek() != Some(')') {
+                    return Err(self.expected("`)`"));
+                }
+
+                self.position += 1;
+                Ok(expr)
+            }
+            Some(c) if c.is_ascii_digit() || c == '.' => {
+                let start = self.position;
+                let number = self.take_while(|c| c.is_ascii_digit() || c == '.');
+
+                number
+                    .parse()
+                    .map(Expr::Number)
+                    .map_err(|_| FormulaError::InvalidNumber {
+                        position: start,
+                        number: number.to_string(),
+                    })
+            }
+            Some(c) if c.is_alphabetic() || c == '_' => {
+                Ok(Expr::Variable(self.identifier().to_string()))
+            }
+            _ => Err(self.expected("a number, variable or `(`")),
+        }
+    }
+}
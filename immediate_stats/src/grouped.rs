@@ -0,0 +1,224 @@
+//! Contains a stat with non-stacking groups of modifiers.
+
+use crate::StatContainer;
+use crate::modifier::Modifier;
+use crate::stat::Stat;
+use crate::value::StatValue;
+use crate::visit::{StatVisitor, StatVisitorMut};
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+use std::ops::{AddAssign, DivAssign, MulAssign, SubAssign};
+
+/// How the modifiers in a [`ModifierGroup`] are combined.
+#[derive(PartialEq, Eq, Debug, Copy, Clone, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "bevy",
+    derive(bevy_reflect::Reflect),
+    reflect(PartialEq, Debug, Clone, Hash)
+)]
+pub enum GroupPolicy {
+    /// Only the strongest modifier is used, based on its effect on the stat's base.
+    /// See [`Modifier::cmp_at`].
+    Max,
+    /// Only the weakest modifier is used, based on its effect on the stat's base.
+    /// This is useful for slows, where only the strongest slow should apply.
+    Min,
+    /// Bonuses are added, and multipliers are summed as percentages,
+    /// so two `x1.1` multipliers result in `x1.2`.
+    Sum,
+    /// Bonuses are added, and multipliers compound.
+    /// This is the same as applying each modifier directly.
+    Product,
+}
+
+/// A group of modifiers that have been resolved into a single [`Modifier`] using a [`GroupPolicy`].
+#[derive(PartialEq, Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "bevy",
+    derive(bevy_reflect::Reflect),
+    reflect(PartialEq, Debug, Clone),
+    reflect(where K: PartialEq + std::fmt::Debug + Clone)
+)]
+pub struct ModifierGroup<T: StatValue = i32, K = &'static str> {
+    /// The key used to identify the group.
+    pub key: K,
+    /// How modifiers in the group are combined.
+    /// This is set by the first modifier applied to the group.
+    pub policy: GroupPolicy,
+    /// The resolved modifier for the group.
+    pub modifier: Modifier<T>,
+}
+
+impl<T: StatValue, K> ModifierGroup<T, K> {
+    /// Combines a new modifier into the group using the group's policy.
+    ///
+    /// For [`Max`](GroupPolicy::Max) and [`Min`](GroupPolicy::Min), modifiers are compared
+    /// using `base`, and the current modifier is kept if they are equal.
+    fn combine(&mut self, modifier: Modifier<T>, base: T) {
+        let current = &mut self.modifier;
+
+        match self.policy {
+            GroupPolicy::Max => {
+                if modifier.cmp_at(current, base) == Some(Ordering::Greater) {
+                    *current = modifier;
+                }
+            }
+            GroupPolicy::Min => {
+                if modifier.cmp_at(current, base) == Some(Ordering::Less) {
+                    *current = modifier;
+                }
+            }
+            GroupPolicy::Sum => *current = *current + modifier,
+            GroupPolicy::Product => *current = *current * modifier,
+        }
+    }
+}
+
+/// A [`Stat`] that can have modifiers applied under a group key, so that they don't stack.
+///
+/// Each group is resolved into a single modifier using its [`GroupPolicy`],
+/// before being combined with the rest of the stat during [calculation](GroupedStat::total).
+/// For example, two slows in a [`Min`](GroupPolicy::Min) group will only apply the strongest one.
+///
+/// Modifiers applied directly to the [inner stat](GroupedStat::stat), or using operators such as
+/// [`+=`](GroupedStat::add_assign), stack like normal.
+/// All groups are removed when [`reset_modifiers`][reset] is called.
+///
+/// [reset]: StatContainer::reset_modifiers
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "bevy",
+    derive(bevy_reflect::Reflect),
+    reflect(PartialEq, Debug, Clone),
+    reflect(where K: PartialEq + std::fmt::Debug + Clone)
+)]
+pub struct GroupedStat<T: StatValue = i32, K = &'static str> {
+    /// The stat that ungrouped modifiers are applied to.
+    pub stat: Stat<T>,
+    groups: Vec<ModifierGroup<T, K>>,
+}
+
+impl<T: StatValue, K: PartialEq> GroupedStat<T, K> {
+    /// Creates a new grouped stat from a base value.
+    pub fn new(base: T) -> Self {
+        Self::from(Stat::new(base))
+    }
+
+    /// Calculates the total value of the stat, after resolving all groups. See [`Stat::total`].
+    pub fn total(&self) -> T {
+        self.resolved().total()
+    }
+
+    /// Returns the inner stat with the modifier of every group applied to it.
+    pub fn resolved(&self) -> Stat<T> {
+        let mut stat = self.stat;
+
+        for group in &self.groups {
+            stat.apply(group.modifier);
+        }
+
+        stat
+    }
+
+    /// Returns every group that has been applied since the last reset.
+    pub fn groups(&self) -> &[ModifierGroup<T, K>] {
+        &self.groups
+    }
+
+    /// Returns the group with the given key, if it exists.
+    pub fn group(&self, key: &K) -> Option<&ModifierGroup<T, K>> {
+        self.groups.iter().find(|group| &group.key == key)
+    }
+
+    /// Applies the [`Modifier`] under a group key.
+    ///
+    /// If this is the first modifier in the group, the group is created using the given policy.
+    /// Otherwise, the modifier is combined using the group's existing policy.
+    pub fn apply_grouped(&mut self, key: K, policy: GroupPolicy, modifier: Modifier<T>) {
+        match self.groups.iter_mut().find(|group| group.key == key) {
+            Some(group) => group.combine(modifier, self.stat.base),
+            None => self.groups.push(ModifierGroup {
+                key,
+                policy,
+                modifier,
+            }),
+        }
+    }
+}
+
+impl<T: StatValue, K> StatContainer for GroupedStat<T, K> {
+    fn reset_modifiers(&mut self) {
+        self.stat.reset_modifiers();
+        self.groups.clear();
+    }
+
+    fn visit_stats(&self, visitor: &mut StatVisitor<'_>) {
+        self.stat.visit_stats(visitor);
+    }
+
+    fn visit_stats_mut(&mut self, visitor: &mut StatVisitorMut<'_>) {
+        self.stat.visit_stats_mut(visitor);
+    }
+}
+
+impl<T: StatValue, K> Default for GroupedStat<T, K> {
+    fn default() -> Self {
+        Self::from(Stat::default())
+    }
+}
+
+impl<T: StatValue, K> From<Stat<T>> for GroupedStat<T, K> {
+    fn from(stat: Stat<T>) -> Self {
+        Self {
+            stat,
+            groups: Vec::new(),
+        }
+    }
+}
+
+impl<T: StatValue, K> AddAssign<T> for GroupedStat<T, K> {
+    /// Adds to the inner stat's bonus.
+    fn add_assign(&mut self, rhs: T) {
+        self.stat += rhs;
+    }
+}
+
+impl<T: StatValue, K> SubAssign<T> for GroupedStat<T, K> {
+    /// Subtracts from the inner stat's bonus.
+    fn sub_assign(&mut self, rhs: T) {
+        self.stat -= rhs;
+    }
+}
+
+impl<T: StatValue, K> MulAssign<f32> for GroupedStat<T, K> {
+    /// Multiplies the inner stat's multiplier.
+    #[track_caller]
+    fn mul_assign(&mut self, rhs: f32) {
+        self.stat *= rhs;
+    }
+}
+
+impl<T: StatValue, K> DivAssign<f32> for GroupedStat<T, K> {
+    /// Divides the inner stat's multiplier.
+    #[track_caller]
+    fn div_assign(&mut self, rhs: f32) {
+        self.stat /= rhs;
+    }
+}
+
+impl<T: StatValue, K: Display> Display for GroupedStat<T, K> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.stat.fmt(f)?;
+
+        for group in &self.groups {
+            write!(f, " {{{}: ", group.key)?;
+            group.modifier.fmt(f)?;
+            write!(f, "}}")?;
+        }
+
+        Ok(())
+    }
+}
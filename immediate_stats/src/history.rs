@@ -0,0 +1,151 @@
+//! Contains a rolling history of a stat container, for graphs and telemetry.
+
+use crate::StatContainer;
+use crate::value::StatValue;
+use crate::visit::{StatVisitor, StatVisitorMut};
+use std::collections::VecDeque;
+
+/// Records a snapshot of a [`StatContainer`] every time [`reset_modifiers`][reset] is called,
+/// keeping only the most recent snapshots.
+///
+/// Since modifiers are usually reset at the start of each iteration,
+/// each snapshot contains the final values of every stat for one iteration,
+/// including their bonuses and multipliers.
+/// Values can be queried by passing a function that samples each snapshot:
+/// ```rust
+/// # use immediate_stats::*;
+/// #[derive(StatContainer, Clone)]
+/// struct Combat {
+///     damage: Stat,
+///     attack_speed: Stat<f32>,
+/// }
+///
+/// let combat = Combat {
+///     damage: Stat::new(10),
+///     attack_speed: Stat::new(1.0),
+/// };
+/// let mut history = StatHistory::new(combat, 60);
+///
+/// for bonus in 0..5 {
+///     history.container.damage += bonus;
+///     history.reset_modifiers();
+/// }
+///
+/// assert_eq!(history.min(|combat| combat.damage.total()), Some(10));
+/// assert_eq!(history.max(|combat| combat.damage.total()), Some(14));
+/// assert_eq!(history.average(|combat| combat.damage.total()), Some(12.0));
+/// assert_eq!(history.samples(|combat| combat.damage.bonus), vec![0, 1, 2, 3, 4]);
+/// ```
+///
+/// [reset]: StatContainer::reset_modifiers
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StatHistory<C> {
+    /// The container that is recorded.
+    pub container: C,
+    snapshots: VecDeque<C>,
+    capacity: usize,
+}
+
+impl<C: StatContainer + Clone> StatHistory<C> {
+    /// Creates a new history that keeps up to `capacity` snapshots of the container.
+    pub fn new(container: C, capacity: usize) -> Self {
+        Self {
+            container,
+            snapshots: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Returns the maximum number of snapshots that are kept.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of snapshots that have been recorded.
+    pub fn len(&self) -> usize {
+        self.snapshots.len()
+    }
+
+    /// Returns true if no snapshots have been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.snapshots.is_empty()
+    }
+
+    /// Removes all recorded snapshots.
+    pub fn clear(&mut self) {
+        self.snapshots.clear();
+    }
+
+    /// Returns every recorded snapshot, from oldest to newest.
+    pub fn snapshots(&self) -> impl DoubleEndedIterator<Item = &C> + ExactSizeIterator {
+        self.snapshots.iter()
+    }
+
+    /// Returns the most recently recorded snapshot.
+    pub fn latest(&self) -> Option<&C> {
+        self.snapshots.back()
+    }
+
+    /// Samples every snapshot, from oldest to newest, such as for exporting to a graph.
+    pub fn samples<S>(&self, sample: impl Fn(&C) -> S) -> Vec<S> {
+        self.snapshots.iter().map(sample).collect()
+    }
+
+    /// Returns the lowest sampled value, or `None` if there are no snapshots.
+    pub fn min<T: StatValue>(&self, sample: impl Fn(&C) -> T) -> Option<T> {
+        self.snapshots
+            .iter()
+            .map(sample)
+            .reduce(|min, value| if value < min { value } else { min })
+    }
+
+    /// Returns the highest sampled value, or `None` if there are no snapshots.
+    pub fn max<T: StatValue>(&self, sample: impl Fn(&C) -> T) -> Option<T> {
+        self.snapshots
+            .iter()
+            .map(sample)
+            .reduce(|max, value| if value > max { value } else { max })
+    }
+
+    /// Returns the unrounded average of the sampled values, or `None` if there are no snapshots.
+    pub fn average<T: StatValue>(&self, sample: impl Fn(&C) -> T) -> Option<T::Exact> {
+        let sum = self
+            .snapshots
+            .iter()
+            .map(|snapshot| sample(snapshot).to_exact())
+            .reduce(|sum, value| sum + value)?;
+
+        Some(sum / (self.snapshots.len() as f32).into())
+    }
+}
+
+impl<C: StatContainer + Clone> StatContainer for StatHistory<C> {
+    /// Records a snapshot of the container, and then resets it.
+    ///
+    /// If the history is full, the oldest snapshot is removed.
+    fn reset_modifiers(&mut self) {
+        if self.capacity > 0 {
+            if self.snapshots.len() >= self.capacity {
+                self.snapshots.pop_front();
+            }
+
+            self.snapshots.push_back(self.container.clone());
+        }
+
+        self.container.reset_modifiers();
+    }
+
+    fn update_derived(&mut self) {
+        self.container.update_derived();
+    }
+
+    /// Visits the current container, but not the snapshots.
+    fn visit_stats(&self, visitor: &mut StatVisitor<'_>) {
+        self.container.visit_stats(visitor);
+    }
+
+    fn visit_stats_mut(&mut self, visitor: &mut StatVisitorMut<'_>) {
+        self.container.visit_stats_mut(visitor);
+    }
+}
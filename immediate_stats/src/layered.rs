@@ -0,0 +1,350 @@
+//! Contains a stat with separate layers of modifiers.
+
+use crate::StatContainer;
+use crate::error::debug_assert_finite;
+use crate::modifier::Modifier;
+use crate::parse::ParseStatError;
+use crate::value::{Rounding, StatValue};
+use crate::visit::{AnyModifier, AnyStat, StatVisitor, StatVisitorMut};
+use std::fmt::{Display, Formatter};
+use std::ops::{AddAssign, DivAssign, MulAssign, SubAssign};
+
+/// A layer of bonuses in a [`LayeredStat`].
+#[derive(PartialEq, Eq, Debug, Copy, Clone, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "bevy",
+    derive(bevy_reflect::Reflect),
+    reflect(PartialEq, Debug, Clone, Hash)
+)]
+pub enum FlatLayer {
+    /// Bonuses that are added to the base, before any multipliers.
+    Flat,
+    /// Bonuses that are added after all multipliers.
+    FinalFlat,
+}
+
+/// A layer of multipliers in a [`LayeredStat`].
+#[derive(PartialEq, Eq, Debug, Copy, Clone, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "bevy",
+    derive(bevy_reflect::Reflect),
+    reflect(PartialEq, Debug, Clone, Hash)
+)]
+pub enum MultiplierLayer {
+    /// Multipliers that are summed together, so two `x1.1` multipliers result in `x1.2`.
+    Increased,
+    /// Multipliers that compound, so two `x1.1` multipliers result in `x1.21`.
+    More,
+}
+
+/// A stat that [resets][reset] to a base value every iteration,
+/// with a separate accumulator for each layer of modifiers.
+///
+/// The layers are calculated in the order [`Flat`](FlatLayer::Flat),
+/// [`Increased`](MultiplierLayer::Increased), [`More`](MultiplierLayer::More),
+/// and [`FinalFlat`](FlatLayer::FinalFlat), so the total is
+/// `((base + flat) x (1 + increased) x more) + final_flat`.
+/// Each flat layer also includes a percentage of the base.
+///
+/// Like a [`Stat`](crate::Stat), [`+=`][add] and [`-=`][sub] modify the [`Flat`](FlatLayer::Flat) layer,
+/// while [`*=`][mul] and [`/=`][div] modify the [`More`](MultiplierLayer::More) layer.
+/// Other layers can be targeted using [`LayeredStat::apply_to`], [`LayeredStat::add_to`],
+/// and [`LayeredStat::multiply_in`].
+/// All layers are reset when [`reset_modifiers`][reset] is called.
+///
+/// [reset]: StatContainer::reset_modifiers
+/// [add]: LayeredStat::add_assign
+/// [sub]: LayeredStat::sub_assign
+/// [mul]: LayeredStat::mul_assign
+/// [div]: LayeredStat::div_assign
+#[derive(PartialEq, Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "bevy",
+    derive(bevy_reflect::Reflect),
+    reflect(PartialEq, Debug, Clone)
+)]
+pub struct LayeredStat<T: StatValue = i32> {
+    /// The persistent value of the stat.
+    /// After being [reset](StatContainer::reset_modifiers), [`LayeredStat::total`] will be equal to `base`.
+    pub base: T,
+    /// The sum of the [`Flat`](FlatLayer::Flat) layer. Gets reset to zero.
+    ///
+    /// This is [signed](StatValue::Bonus), so unsigned stats can be debuffed.
+    pub flat: T::Bonus,
+    /// A percentage of `base` that is part of the [`Flat`](FlatLayer::Flat) layer,
+    /// such as `10.0` for +10% of base. Gets reset to zero.
+    ///
+    /// This is resolved during calculation, so it stays correct if `base` changes.
+    pub base_percent: f32,
+    /// The sum of the [`Increased`](MultiplierLayer::Increased) layer, as a fraction. Gets reset to zero.
+    ///
+    /// For example, `0.2` represents a `x1.2` multiplier.
+    pub increased: f32,
+    /// The product of the [`More`](MultiplierLayer::More) layer. Gets reset to one.
+    pub more: f32,
+    /// The sum of the [`FinalFlat`](FlatLayer::FinalFlat) layer. Gets reset to zero.
+    pub final_flat: T::Bonus,
+    /// A percentage of `base` that is part of the [`FinalFlat`](FlatLayer::FinalFlat) layer.
+    /// Gets reset to zero.
+    pub final_base_percent: f32,
+    /// How [`LayeredStat::total`] is rounded after multipliers are applied. This does **not** get reset.
+    pub rounding: Rounding,
+}
+
+impl<T: StatValue> LayeredStat<T> {
+    /// Creates a new layered stat from a base value.
+    pub fn new(base: T) -> Self {
+        Self {
+            base,
+            ..Default::default()
+        }
+    }
+
+    /// Calculates the total value of the stat.
+    ///
+    /// The result is rounded using the stat's [`Rounding`] strategy,
+    /// and saturates at the bounds of the value type.
+    pub fn total(&self) -> T {
+        let base = self.base.to_bonus();
+        let sum = (base + self.flat).to_exact()
+            + base.to_exact() * T::Bonus::exact_from_f32(self.base_percent / 100.0);
+        let scaled = sum * T::Bonus::exact_from_f32(self.multiplier())
+            + base.to_exact() * T::Bonus::exact_from_f32(self.final_base_percent / 100.0);
+        T::from_bonus(T::Bonus::from_exact(scaled, self.rounding) + self.final_flat)
+    }
+
+    /// Calculates the unrounded total value of the stat.
+    pub fn total_exact(&self) -> T::Exact {
+        let base = self.base.to_exact();
+        let sum = T::bonus_to_exact(self.base.to_bonus() + self.flat)
+            + base * T::exact_from_f32(self.base_percent / 100.0);
+        sum * T::exact_from_f32(self.multiplier())
+            + base * T::exact_from_f32(self.final_base_percent / 100.0)
+            + T::bonus_to_exact(self.final_flat)
+    }
+
+    /// The combined multiplier of the [`Increased`](MultiplierLayer::Increased)
+    /// and [`More`](MultiplierLayer::More) layers.
+    fn multiplier(&self) -> f32 {
+        (1.0 + self.increased) * self.more
+    }
+
+    /// A builder that overwrites the [`Flat`](FlatLayer::Flat) layer with a new value.
+    pub fn with_flat(mut self, flat: T::Bonus) -> Self {
+        self.flat = flat;
+        self
+    }
+
+    /// A builder that overwrites the percentage of base in the [`Flat`](FlatLayer::Flat) layer.
+    pub fn with_base_percent(mut self, base_percent: f32) -> Self {
+        self.base_percent = base_percent;
+        self
+    }
+
+    /// A builder that overwrites the [`Increased`](MultiplierLayer::Increased) layer with a new value.
+    pub fn with_increased(mut self, increased: f32) -> Self {
+        self.increased = increased;
+        self
+    }
+
+    /// A builder that overwrites the [`More`](MultiplierLayer::More) layer with a new value.
+    pub fn with_more(mut self, more: f32) -> Self {
+        self.more = more;
+        self
+    }
+
+    /// A builder that overwrites the [`FinalFlat`](FlatLayer::FinalFlat) layer with a new value.
+    pub fn with_final_flat(mut self, final_flat: T::Bonus) -> Self {
+        self.final_flat = final_flat;
+        self
+    }
+
+    /// A builder that overwrites the percentage of base in the [`FinalFlat`](FlatLayer::FinalFlat) layer.
+    pub fn with_final_base_percent(mut self, final_base_percent: f32) -> Self {
+        self.final_base_percent = final_base_percent;
+        self
+    }
+
+    /// A builder that overwrites the rounding strategy of the total.
+    pub fn with_rounding(mut self, rounding: Rounding) -> Self {
+        self.rounding = rounding;
+        self
+    }
+
+    /// Applies the [`Modifier`] values to the [`Flat`](FlatLayer::Flat) and [`More`](MultiplierLayer::More) layers.
+    ///
+    /// This is equivalent to [`Stat::apply`](crate::Stat::apply).
+    pub fn apply(&mut self, modifier: Modifier<T>) {
+        self.apply_to(FlatLayer::Flat, MultiplierLayer::More, modifier);
+    }
+
+    /// Applies the [`Modifier`] values to specific layers.
+    ///
+    /// The bonus and [base percent](Modifier::base_percent) are added to the `flat` layer,
+    /// and the multiplier is applied to the `multiplier` layer.
+    /// ```rust
+    /// # use immediate_stats::*;
+    /// let mut stat = LayeredStat::new(100);
+    /// stat.apply_to(FlatLayer::FinalFlat, MultiplierLayer::Increased, Modifier::new(5, 1.2));
+    /// stat.apply_to(FlatLayer::Flat, MultiplierLayer::Increased, Modifier::new(10, 1.3));
+    ///
+    /// // ((100 + 10) x (1 + 0.2 + 0.3)) + 5 = 170
+    /// assert_eq!(stat.total(), 170);
+    /// ```
+    pub fn apply_to(
+        &mut self,
+        flat: FlatLayer,
+        multiplier: MultiplierLayer,
+        modifier: Modifier<T>,
+    ) {
+        self.add_to(flat, modifier.bonus);
+
+        match flat {
+            FlatLayer::Flat => self.base_percent += modifier.base_percent,
+            FlatLayer::FinalFlat => self.final_base_percent += modifier.base_percent,
+        }
+
+        self.multiply_in(multiplier, modifier.multiplier);
+    }
+
+    /// Adds a bonus to a flat layer.
+    pub fn add_to(&mut self, layer: FlatLayer, bonus: T::Bonus) {
+        match layer {
+            FlatLayer::Flat => self.flat += bonus,
+            FlatLayer::FinalFlat => self.final_flat += bonus,
+        }
+    }
+
+    /// Applies a multiplier to a multiplier layer.
+    ///
+    /// In debug builds, this panics if the resulting multiplier is NaN or infinite.
+    #[track_caller]
+    pub fn multiply_in(&mut self, layer: MultiplierLayer, multiplier: f32) {
+        match layer {
+            MultiplierLayer::Increased => self.increased += multiplier - 1.0,
+            MultiplierLayer::More => self.more *= multiplier,
+        }
+
+        debug_assert_finite(self.multiplier());
+    }
+}
+
+impl<T: StatValue> StatContainer for LayeredStat<T> {
+    fn reset_modifiers(&mut self) {
+        self.flat = T::Bonus::default();
+        self.base_percent = 0.0;
+        self.increased = 0.0;
+        self.more = 1.0;
+        self.final_flat = T::Bonus::default();
+        self.final_base_percent = 0.0;
+    }
+
+    fn visit_stats(&self, visitor: &mut StatVisitor<'_>) {
+        visitor.visit(self);
+    }
+
+    fn visit_stats_mut(&mut self, visitor: &mut StatVisitorMut<'_>) {
+        visitor.visit(self);
+    }
+}
+
+impl<T: StatValue> AnyStat for LayeredStat<T> {
+    fn multiply(&mut self, multiplier: f32) {
+        *self *= multiplier;
+    }
+
+    fn parse_modifier(&self, text: &str) -> Result<AnyModifier, ParseStatError> {
+        text.parse::<Modifier<T>>().map(AnyModifier::new)
+    }
+
+    fn apply_modifier(&mut self, modifier: &AnyModifier) -> bool {
+        modifier
+            .downcast_ref::<Modifier<T>>()
+            .map(|modifier| self.apply(*modifier))
+            .is_some()
+    }
+}
+
+impl<T: StatValue> Default for LayeredStat<T> {
+    fn default() -> Self {
+        Self {
+            base: T::default(),
+            flat: T::Bonus::default(),
+            base_percent: 0.0,
+            increased: 0.0,
+            more: 1.0,
+            final_flat: T::Bonus::default(),
+            final_base_percent: 0.0,
+            rounding: Rounding::default(),
+        }
+    }
+}
+
+impl<T: StatValue> AddAssign<T> for LayeredStat<T> {
+    /// Adds to the stat's [`Flat`](FlatLayer::Flat) layer.
+    fn add_assign(&mut self, rhs: T) {
+        self.flat += rhs.to_bonus();
+    }
+}
+
+impl<T: StatValue> SubAssign<T> for LayeredStat<T> {
+    /// Subtracts from the stat's [`Flat`](FlatLayer::Flat) layer.
+    fn sub_assign(&mut self, rhs: T) {
+        self.flat -= rhs.to_bonus();
+    }
+}
+
+impl<T: StatValue> MulAssign<f32> for LayeredStat<T> {
+    /// Multiplies the stat's [`More`](MultiplierLayer::More) layer.
+    ///
+    /// In debug builds, this panics if the resulting multiplier is NaN or infinite.
+    #[track_caller]
+    fn mul_assign(&mut self, rhs: f32) {
+        self.more *= rhs;
+        debug_assert_finite(self.more);
+    }
+}
+
+impl<T: StatValue> DivAssign<f32> for LayeredStat<T> {
+    /// Divides the stat's [`More`](MultiplierLayer::More) layer.
+    ///
+    /// In debug builds, this panics if the resulting multiplier is NaN or infinite.
+    #[track_caller]
+    fn div_assign(&mut self, rhs: f32) {
+        self.more /= rhs;
+        debug_assert_finite(self.more);
+    }
+}
+
+impl<T: StatValue> Display for LayeredStat<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "(({} + {}", self.base, self.flat)?;
+
+        if self.base_percent != 0.0 {
+            write!(f, " + {}%", self.base_percent)?;
+        }
+
+        if let Some(precision) = f.precision() {
+            write!(
+                f,
+                ") x (1 + {:.*}) x {:.*}) + {}",
+                precision, self.increased, precision, self.more, self.final_flat
+            )
+        } else {
+            write!(
+                f,
+                ") x (1 + {}) x {}) + {}",
+                self.increased, self.more, self.final_flat
+            )
+        }?;
+
+        if self.final_base_percent != 0.0 {
+            write!(f, " + {}%", self.final_base_percent)?;
+        }
+
+        Ok(())
+    }
+}
@@ -23,6 +23,26 @@
 //! }
 //! ```
 //!
+//! ## Fixed Point
+//!
+//! Multipliers are stored as floats, which can give different results on different platforms.
+//! For lockstep networking or replays, the `fixed_point` feature flag adds `FixedStat`,
+//! which uses fixed-point multipliers so that the same modifiers always produce the same total.
+//!
+//! ## Serde
+//!
+//! The `serde` feature flag implements `Serialize` and `Deserialize` for stats and modifiers,
+//! which includes every field, such as bonuses and multipliers.
+//! To skip the modifiers that are reset every iteration,
+//! use `#[serde(with = "immediate_stats::skip_temporary")]`.
+//!
+//! ## Buffs
+//!
+//! The `buffs` feature flag adds `BuffSchema`, which loads named sets of modifiers
+//! from text such as `haste: { speed: x1.3 }`, or from RON and JSON.
+//! Stats are named by their path in a [`StatContainer`], such as `movement.speed`.
+//! This lets designers tweak buffs without recompiling.
+//!
 //! ## Bevy
 //!
 //! There is build-in integration with the [Bevy Engine](https://bevyengine.org)
@@ -83,11 +103,35 @@
 
 #[cfg(feature = "bevy")]
 mod bevy;
+#[cfg(feature = "buffs")]
+mod buff;
+mod derived;
+mod easing;
+mod error;
+#[cfg(feature = "fixed_point")]
+mod fixed;
+mod formula;
+mod grouped;
+mod history;
+mod layered;
+mod map;
 mod modifier;
+mod parse;
+mod pool;
+#[cfg(feature = "serde")]
+pub mod skip_temporary;
+mod snapshot;
+mod stacked;
 mod stat;
+mod tracked;
+mod value;
+mod vector;
+mod visit;
 
 /// Implements [`reset_modifiers`](StatContainer::reset_modifiers)
 /// by propagating the call down to any stat fields.
+/// Stat fields are also [visited](StatContainer::visit_stats) using their field name,
+/// such as `max` or `0`.
 /// ```rust
 /// # use immediate_stats::*;
 /// #[derive(StatContainer, Default, Debug, PartialEq)]
@@ -141,13 +185,52 @@ mod stat;
 ///     assert_eq!(partial.ignored, Stat::default().with_bonus(10));
 /// }
 /// ```
+/// # Derived Stats
+/// A [`DerivedStat`] field can use `#[derived(...)]` to calculate its base from other fields.
+/// The expression is evaluated by [`update_derived`](StatContainer::update_derived),
+/// and derived fields are updated after any derived fields that they use.
+/// This attribute is only supported on structs.
+/// ```rust
+/// # use immediate_stats::*;
+/// #[derive(StatContainer)]
+/// struct Attributes {
+///     #[derived(self.attack.total() / 10)]
+///     crit_chance: DerivedStat, // Updated after `attack`, even though it is declared first.
+///     #[derived(self.strength.total() * 2)]
+///     attack: DerivedStat,
+///     strength: Stat,
+/// }
+/// ```
 pub use immediate_stats_macros::StatContainer;
+
+pub use derived::*;
+pub use easing::*;
+pub use error::*;
+pub use formula::*;
+pub use grouped::*;
+pub use history::*;
+pub use layered::*;
+pub use map::*;
 pub use modifier::*;
+pub use parse::*;
+pub use pool::*;
+pub use snapshot::*;
+pub use stacked::*;
 pub use stat::*;
+pub use tracked::*;
+pub use value::*;
+pub use vector::*;
+pub use visit::*;
 
 #[cfg(feature = "bevy")]
 pub use bevy::*;
 
+#[cfg(feature = "buffs")]
+pub use buff::*;
+
+#[cfg(feature = "fixed_point")]
+pub use fixed::*;
+
 // Used by derive macro.
 #[cfg(feature = "bevy")]
 #[doc(hidden)]
@@ -160,4 +243,49 @@ pub use bevy_app::prelude::PreUpdate as __PreUpdate;
 pub trait StatContainer {
     /// Resets all stats to a base value. For most use-cases, this should be called every frame/iteration.
     fn reset_modifiers(&mut self);
+
+    /// Recalculates the base of any [derived stats](DerivedStat) from the totals of other stats.
+    ///
+    /// This should be called after modifiers have been applied, but before any totals are read.
+    /// The default implementation does nothing.
+    fn update_derived(&mut self) {}
+
+    /// Passes each stat to the visitor, using [`StatVisitor::field`] for sub-containers.
+    /// This is used to list stats by name, such as for debug UIs or save systems.
+    ///
+    /// The default implementation visits nothing.
+    fn visit_stats(&self, visitor: &mut StatVisitor<'_>) {
+        let _ = visitor;
+    }
+
+    /// Passes each stat to the visitor mutably, using [`StatVisitorMut::field`] for sub-containers.
+    ///
+    /// The default implementation visits nothing.
+    fn visit_stats_mut(&mut self, visitor: &mut StatVisitorMut<'_>) {
+        let _ = visitor;
+    }
+
+    /// Calls `f` with the dotted path of each stat, such as `movement.speed`.
+    fn for_each_stat(&self, mut f: impl FnMut(&str, &dyn AnyStat))
+    where
+        Self: Sized,
+    {
+        self.visit_stats(&mut StatVisitor::new(&mut f));
+    }
+
+    /// Calls `f` with the dotted path of each stat, allowing them to be modified.
+    /// ```rust
+    /// # use immediate_stats::*;
+    /// let mut speed = VectorStat::new([10, 20]);
+    ///
+    /// // Slow everything by 50%.
+    /// speed.for_each_stat_mut(|_, stat| stat.multiply(0.5));
+    /// assert_eq!(speed.total(), [5, 10]);
+    /// ```
+    fn for_each_stat_mut(&mut self, mut f: impl FnMut(&str, &mut dyn AnyStat))
+    where
+        Self: Sized,
+    {
+        self.visit_stats_mut(&mut StatVisitorMut::new(&mut f));
+    }
 }
@@ -0,0 +1,284 @@
+//! Contains a collection of stats that are looked up by key, for stats that are only known at runtime.
+
+use crate::StatContainer;
+use crate::modifier::Modifier;
+use crate::stat::Stat;
+use crate::value::StatValue;
+use crate::visit::{StatVisitor, StatVisitorMut};
+#[cfg(feature = "bevy")]
+use bevy_ecs::reflect::ReflectComponent;
+use std::borrow::Borrow;
+use std::collections::BTreeMap;
+use std::collections::btree_map::{Iter, IterMut};
+use std::fmt::{Display, Formatter};
+use std::ops::{Index, IndexMut};
+
+/// A collection of [stats](Stat) keyed by an enum or a string,
+/// for games where the set of stats is not known at compile time, such as when supporting mods.
+///
+/// Keys that don't have an entry act like a copy of [`StatMap::default`], and an entry is created
+/// from it the first time the key is modified.
+/// Entries are stored in key order, so iteration is deterministic.
+/// ```rust
+/// # use immediate_stats::*;
+/// let mut stats = StatMap::<String>::new(10);
+///
+/// stats["speed"] += 5; // Creates `speed` with a base of 10.
+/// stats.apply("strength".to_string(), Modifier::from_multiplier(2.0));
+///
+/// assert_eq!(stats.total("speed"), 15);
+/// assert_eq!(stats.total("strength"), 20);
+/// assert_eq!(stats.total("luck"), 10); // Missing keys use the default base.
+///
+/// stats.reset_modifiers(); // Resets every entry.
+/// for (key, total) in stats.totals() {
+///     assert_eq!(total, 10);
+/// }
+/// ```
+///
+/// With the `bevy` feature, this can be used directly as a component.
+/// Only maps with `String` keys are registered by [`ImmediateStatsPlugin`](crate::ImmediateStatsPlugin),
+/// so maps with other keys should be registered manually.
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "bevy",
+    derive(bevy_ecs::component::Component, bevy_reflect::Reflect),
+    reflect(Component, PartialEq, Debug, Clone),
+    reflect(where K: Ord + PartialEq + std::fmt::Debug + Clone)
+)]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "K: serde::Serialize, T: serde::Serialize",
+        deserialize = "K: Ord + serde::Deserialize<'de>, T: serde::Deserialize<'de>"
+    ))
+)]
+pub struct StatMap<K, T: StatValue = i32> {
+    /// The stat used for keys without an entry, and copied when a new entry is created.
+    ///
+    /// This allows new entries to share bounds and rounding, as well as a base.
+    pub default: Stat<T>,
+    stats: BTreeMap<K, Stat<T>>,
+}
+
+impl<K: Ord, T: StatValue> StatMap<K, T> {
+    /// Creates an empty map, where missing keys use the given base.
+    pub fn new(default_base: T) -> Self {
+        Self::from(Stat::new(default_base))
+    }
+
+    /// Inserts a copy of the [default](StatMap::default) stat with the given base,
+    /// replacing any existing entry.
+    pub fn with_base(mut self, key: K, base: T) -> Self {
+        self.insert(
+            key,
+            Stat {
+                base,
+                ..self.default
+            },
+        );
+        self
+    }
+
+    /// Returns the entry for the given key, if it exists.
+    pub fn get<Q: Ord + ?Sized>(&self, key: &Q) -> Option<&Stat<T>>
+    where
+        K: Borrow<Q>,
+    {
+        self.stats.get(key)
+    }
+
+    /// Returns a mutable reference to the entry for the given key, if it exists.
+    pub fn get_mut<Q: Ord + ?Sized>(&mut self, key: &Q) -> Option<&mut Stat<T>>
+    where
+        K: Borrow<Q>,
+    {
+        self.stats.get_mut(key)
+    }
+
+    /// Returns the entry for the given key,
+    /// creating it from [`StatMap::default`] if it does not exist.
+    pub fn entry(&mut self, key: K) -> &mut Stat<T> {
+        let default = self.default;
+        self.stats.entry(key).or_insert(default)
+    }
+
+    /// Inserts a stat, returning the previous entry if there was one.
+    pub fn insert(&mut self, key: K, stat: Stat<T>) -> Option<Stat<T>> {
+        self.stats.insert(key, stat)
+    }
+
+    /// Removes and returns the entry for the given key, if it exists.
+    pub fn remove<Q: Ord + ?Sized>(&mut self, key: &Q) -> Option<Stat<T>>
+    where
+        K: Borrow<Q>,
+    {
+        self.stats.remove(key)
+    }
+
+    /// Returns true if there is an entry for the given key.
+    pub fn contains_key<Q: Ord + ?Sized>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+    {
+        self.stats.contains_key(key)
+    }
+
+    /// Calculates the total of the given key, using [`StatMap::default`] if there is no entry.
+    pub fn total<Q: Ord + ?Sized>(&self, key: &Q) -> T
+    where
+        K: Borrow<Q>,
+    {
+        self[key].total()
+    }
+
+    /// [Applies](Stat::apply) a modifier to the given key,
+    /// creating an entry from [`StatMap::default`] if it does not exist.
+    pub fn apply(&mut self, key: K, modifier: Modifier<T>) {
+        self.entry(key).apply(modifier);
+    }
+
+    /// Returns an iterator over the [total](Stat::total) of each entry, in key order.
+    pub fn totals(&self) -> impl Iterator<Item = (&K, T)> {
+        self.stats.iter().map(|(key, stat)| (key, stat.total()))
+    }
+
+    /// Returns an iterator over each entry, in key order.
+    pub fn iter(&self) -> Iter<'_, K, Stat<T>> {
+        self.stats.iter()
+    }
+
+    /// Returns a mutable iterator over each entry, in key order.
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, Stat<T>> {
+        self.stats.iter_mut()
+    }
+
+    /// Returns an iterator over the keys of each entry, in order.
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.stats.keys()
+    }
+
+    /// Returns the number of entries.
+    pub fn len(&self) -> usize {
+        self.stats.len()
+    }
+
+    /// Returns true if there are no entries.
+    pub fn is_empty(&self) -> bool {
+        self.stats.is_empty()
+    }
+}
+
+impl<K: std::fmt::Debug, T: StatValue> StatContainer for StatMap<K, T> {
+    fn reset_modifiers(&mut self) {
+        self.default.reset_modifiers();
+
+        for stat in self.stats.values_mut() {
+            stat.reset_modifiers();
+        }
+    }
+
+    /// Visits each entry, using its key as the name, such as `["speed"]`.
+    /// [`StatMap::default`] is not visited.
+    fn visit_stats(&self, visitor: &mut StatVisitor<'_>) {
+        for (key, stat) in &self.stats {
+            visitor.entry(key, stat);
+        }
+    }
+
+    fn visit_stats_mut(&mut self, visitor: &mut StatVisitorMut<'_>) {
+        for (key, stat) in &mut self.stats {
+            visitor.entry(key, stat);
+        }
+    }
+}
+
+impl<K, T: StatValue> Default for StatMap<K, T> {
+    fn default() -> Self {
+        Self::from(Stat::default())
+    }
+}
+
+impl<K, T: StatValue> From<Stat<T>> for StatMap<K, T> {
+    /// Creates an empty map, where missing keys use the given stat.
+    fn from(default: Stat<T>) -> Self {
+        Self {
+            default,
+            stats: BTreeMap::new(),
+        }
+    }
+}
+
+impl<K: Ord, T: StatValue> FromIterator<(K, Stat<T>)> for StatMap<K, T> {
+    fn from_iter<I: IntoIterator<Item = (K, Stat<T>)>>(iter: I) -> Self {
+        Self {
+            default: Stat::default(),
+            stats: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl<K: Ord, T: StatValue> Extend<(K, Stat<T>)> for StatMap<K, T> {
+    fn extend<I: IntoIterator<Item = (K, Stat<T>)>>(&mut self, iter: I) {
+        self.stats.extend(iter);
+    }
+}
+
+impl<'a, K, T: StatValue> IntoIterator for &'a StatMap<K, T> {
+    type Item = (&'a K, &'a Stat<T>);
+    type IntoIter = Iter<'a, K, Stat<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.stats.iter()
+    }
+}
+
+impl<'a, K, T: StatValue> IntoIterator for &'a mut StatMap<K, T> {
+    type Item = (&'a K, &'a mut Stat<T>);
+    type IntoIter = IterMut<'a, K, Stat<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.stats.iter_mut()
+    }
+}
+
+impl<K: Borrow<Q> + Ord, Q: Ord + ?Sized, T: StatValue> Index<&Q> for StatMap<K, T> {
+    type Output = Stat<T>;
+
+    /// Returns the entry for the given key, or [`StatMap::default`] if there is no entry.
+    fn index(&self, key: &Q) -> &Stat<T> {
+        self.stats.get(key).unwrap_or(&self.default)
+    }
+}
+
+impl<K: Borrow<Q> + Ord, Q: Ord + ToOwned<Owned = K> + ?Sized, T: StatValue> IndexMut<&Q>
+    for StatMap<K, T>
+{
+    /// Returns the entry for the given key, creating it from [`StatMap::default`] if it does not exist.
+    fn index_mut(&mut self, key: &Q) -> &mut Stat<T> {
+        // Avoid cloning the key when the entry already exists.
+        if !self.stats.contains_key(key) {
+            self.stats.insert(key.to_owned(), self.default);
+        }
+
+        self.stats.get_mut(key).unwrap()
+    }
+}
+
+impl<K: Display, T: StatValue> Display for StatMap<K, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{{")?;
+
+        for (i, (key, stat)) in self.stats.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+
+            write!(f, "{key}: ")?;
+            stat.fmt(f)?;
+        }
+
+        write!(f, "}}")
+    }
+}
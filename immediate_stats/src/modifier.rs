@@ -1,40 +1,85 @@
 //! Contains a modifier that can be applied to [`Stat`](crate::Stat).
 
+use crate::easing::EasingCurve;
+use crate::error::debug_assert_finite;
+use crate::stat::Stat;
+use crate::value::StatValue;
+use std::cmp::Ordering;
 use std::fmt::{Display, Formatter};
-use std::ops::{AddAssign, DivAssign, MulAssign, SubAssign};
+use std::iter::{Product, Sum};
+use std::ops::{Add, AddAssign, DivAssign, Mul, MulAssign, SubAssign};
 
 /// Modifier values that can be [applied](super::Stat::apply) to a [`Stat`](super::Stat).
+///
+/// Modifiers can be combined before being applied:
+/// - `a * b` is the same as applying both `a` and `b`, so bonuses add and multipliers compound.
+///   Collecting an iterator of modifiers, or using [`Product`], combines them this way.
+/// - `a + b` adds bonuses, and sums multipliers as percentages, so two `x1.1` multipliers result in `x1.2`.
+///   Using [`Sum`] combines them this way.
+///
+/// Modifiers are [partially ordered](PartialOrd) by strength,
+/// where one modifier is stronger than another if none of its values are lower.
+/// Modifiers with mixed values, such as a higher bonus but a lower multiplier, can be compared
+/// for a specific base using [`Modifier::cmp_at`].
+/// ```rust
+/// # use immediate_stats::*;
+/// let buffs = vec![Modifier::new(5, 1.5), Modifier::from_multiplier(2.0)];
+/// let combined: Modifier = buffs.into_iter().collect();
+/// assert_eq!(combined, Modifier::new(5, 3.0));
+///
+/// assert!(combined > Modifier::new(5, 2.0));
+/// assert!((combined * combined.inverse()).is_identity());
+/// ```
 #[derive(PartialEq, Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(
     feature = "bevy",
     derive(bevy_reflect::Reflect),
     reflect(PartialEq, Debug, Clone)
 )]
-pub struct Modifier {
+pub struct Modifier<T: StatValue = i32> {
     /// Added to the `base` of a [`Stat`](super::Stat) during calculation.
     ///
+    /// This is [signed](StatValue::Bonus), so unsigned stats can be debuffed.
+    ///
     /// Can be modified using [`+=`](Modifier::add_assign) and [`-=`](`Modifier::sub_assign`).
-    pub bonus: i32,
+    pub bonus: T::Bonus,
+    /// Adds a percentage of the `base` of a [`Stat`](super::Stat), such as `10.0` for +10% of base.
+    ///
+    /// This is resolved when the total is calculated, so it stays correct if the base changes.
+    pub base_percent: f32,
     /// Multiplies the `base` of a [`Stat`](super::Stat) during calculation.
     ///
     /// Can be modified using [`*=`](`Modifier::mul_assign`) and [`/=`](`Modifier::div_assign`).
     pub multiplier: f32,
 }
 
-impl Modifier {
+impl<T: StatValue> Modifier<T> {
     /// Creates a new modifier from a bonus and a multiplier.
-    pub fn new(bonus: i32, multiplier: f32) -> Self {
-        Self { bonus, multiplier }
+    pub fn new(bonus: T::Bonus, multiplier: f32) -> Self {
+        Self {
+            bonus,
+            multiplier,
+            ..Self::default()
+        }
     }
 
     /// Creates a new modifier from a bonus.
-    pub fn from_bonus(bonus: i32) -> Self {
+    pub fn from_bonus(bonus: T::Bonus) -> Self {
         Self {
             bonus,
             ..Self::default()
         }
     }
 
+    /// Creates a new modifier that adds a percentage of the base, such as `10.0` for +10% of base.
+    pub fn from_base_percent(base_percent: f32) -> Self {
+        Self {
+            base_percent,
+            ..Self::default()
+        }
+    }
+
     /// Creates a new modifier from a multiplier.
     pub fn from_multiplier(multiplier: f32) -> Self {
         Self {
@@ -45,58 +90,204 @@ impl Modifier {
 
     /// Returns a new modifier scaled by a fraction.
     ///
-    /// When the scale is zero, the bonus will be zero while the multiplier will be one.
+    /// When the scale is zero, the bonus and base percent will be zero while the multiplier will be one.
     pub fn scaled(&self, fraction: f32) -> Self {
         Self {
-            bonus: (self.bonus as f32 * fraction) as i32,
+            bonus: self.bonus.scale(fraction),
+            base_percent: self.base_percent * fraction,
             multiplier: (1.0 - fraction) * 1.0 + fraction * self.multiplier,
         }
     }
+
+    /// Returns the modifier that cancels this one out when [combined](Modifier::mul) with it.
+    ///
+    /// In debug builds, this panics if the multiplier is zero.
+    #[track_caller]
+    pub fn inverse(&self) -> Self {
+        let inverse = Self {
+            bonus: -self.bonus,
+            base_percent: -self.base_percent,
+            multiplier: 1.0 / self.multiplier,
+        };
+
+        debug_assert_finite(inverse.multiplier);
+        inverse
+    }
+
+    /// Returns true if applying the modifier has no effect.
+    pub fn is_identity(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Compares the strength of two modifiers, based on their effect on a stat with the given base.
+    ///
+    /// Returns `None` if either result is NaN.
+    pub fn cmp_at(&self, other: &Self, base: T) -> Option<Ordering> {
+        let effect = |modifier: &Self| Stat::new(base).with_modifier(*modifier).total_exact();
+        effect(self).partial_cmp(&effect(other))
+    }
+
+    /// Returns a new modifier scaled by a fraction, after remapping it with an [easing curve](EasingCurve).
+    ///
+    /// For curves that map zero to zero and one to one, such as [`Easing`](crate::Easing),
+    /// the endpoints are the same as [`Modifier::scaled`].
+    pub fn scaled_with(&self, fraction: f32, curve: &impl EasingCurve) -> Self {
+        self.scaled(curve.ease(fraction))
+    }
 }
 
-impl Default for Modifier {
+impl<T: StatValue> Default for Modifier<T> {
     fn default() -> Self {
         Self {
-            bonus: 0,
+            bonus: T::Bonus::default(),
+            base_percent: 0.0,
             multiplier: 1.0,
         }
     }
 }
 
-impl AddAssign<i32> for Modifier {
+impl<T: StatValue> AddAssign<T> for Modifier<T> {
     /// Adds to the modifier's bonus.
-    fn add_assign(&mut self, rhs: i32) {
-        self.bonus += rhs;
+    fn add_assign(&mut self, rhs: T) {
+        self.bonus += rhs.to_bonus();
     }
 }
 
-impl SubAssign<i32> for Modifier {
+impl<T: StatValue> SubAssign<T> for Modifier<T> {
     /// Subtracts from the modifier's bonus.
-    fn sub_assign(&mut self, rhs: i32) {
-        self.bonus -= rhs;
+    fn sub_assign(&mut self, rhs: T) {
+        self.bonus -= rhs.to_bonus();
     }
 }
 
-impl MulAssign<f32> for Modifier {
+impl<T: StatValue> MulAssign<f32> for Modifier<T> {
     /// Multiplies the modifier's multiplier.
+    ///
+    /// In debug builds, this panics if the resulting multiplier is NaN or infinite.
+    #[track_caller]
     fn mul_assign(&mut self, rhs: f32) {
         self.multiplier *= rhs;
+        debug_assert_finite(self.multiplier);
     }
 }
 
-impl DivAssign<f32> for Modifier {
+impl<T: StatValue> DivAssign<f32> for Modifier<T> {
     /// Divides the modifier's multiplier.
+    ///
+    /// In debug builds, this panics if the resulting multiplier is NaN or infinite.
+    #[track_caller]
     fn div_assign(&mut self, rhs: f32) {
         self.multiplier /= rhs;
+        debug_assert_finite(self.multiplier);
     }
 }
 
-impl Display for Modifier {
+impl<T: StatValue> Add for Modifier<T> {
+    type Output = Self;
+
+    /// Combines two modifiers by adding their bonuses and summing their multipliers as percentages,
+    /// so two `x1.1` multipliers result in `x1.2`.
+    fn add(self, rhs: Self) -> Self::Output {
+        Self {
+            bonus: self.bonus + rhs.bonus,
+            base_percent: self.base_percent + rhs.base_percent,
+            multiplier: self.multiplier + rhs.multiplier - 1.0,
+        }
+    }
+}
+
+impl<T: StatValue> Mul for Modifier<T> {
+    type Output = Self;
+
+    /// Combines two modifiers by adding their bonuses and multiplying their multipliers.
+    /// This is the same as applying both modifiers.
+    ///
+    /// In debug builds, this panics if the resulting multiplier is NaN or infinite.
+    #[track_caller]
+    fn mul(self, rhs: Self) -> Self::Output {
+        let product = Self {
+            bonus: self.bonus + rhs.bonus,
+            base_percent: self.base_percent + rhs.base_percent,
+            multiplier: self.multiplier * rhs.multiplier,
+        };
+
+        debug_assert_finite(product.multiplier);
+        product
+    }
+}
+
+impl<T: StatValue> Sum for Modifier<T> {
+    /// Combines all modifiers using [`Add`], so multipliers are summed as percentages.
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::default(), Add::add)
+    }
+}
+
+impl<'a, T: StatValue> Sum<&'a Modifier<T>> for Modifier<T> {
+    /// Combines all modifiers using [`Add`], so multipliers are summed as percentages.
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
+
+impl<T: StatValue> Product for Modifier<T> {
+    /// Combines all modifiers using [`Mul`], which is the same as applying each of them.
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::default(), Mul::mul)
+    }
+}
+
+impl<'a, T: StatValue> Product<&'a Modifier<T>> for Modifier<T> {
+    /// Combines all modifiers using [`Mul`], which is the same as applying each of them.
+    fn product<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.copied().product()
+    }
+}
+
+impl<T: StatValue> FromIterator<Modifier<T>> for Modifier<T> {
+    /// Combines all modifiers using [`Mul`], which is the same as applying each of them.
+    fn from_iter<I: IntoIterator<Item = Self>>(iter: I) -> Self {
+        iter.into_iter().product()
+    }
+}
+
+impl<T: StatValue> PartialOrd for Modifier<T> {
+    /// Compares the strength of two modifiers.
+    ///
+    /// A modifier is stronger if none of its values are lower, and at least one is higher.
+    /// Returns `None` if some values are higher while others are lower.
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        let orderings = [
+            self.bonus.partial_cmp(&other.bonus)?,
+            self.base_percent.partial_cmp(&other.base_percent)?,
+            self.multiplier.partial_cmp(&other.multiplier)?,
+        ];
+
+        orderings
+            .into_iter()
+            .try_fold(Ordering::Equal, |combined, ordering| {
+                match (combined, ordering) {
+                    (Ordering::Equal, ordering) => Some(ordering),
+                    (combined, Ordering::Equal) => Some(combined),
+                    (combined, ordering) if combined == ordering => Some(combined),
+                    _ => None,
+                }
+            })
+    }
+}
+
+impl<T: StatValue> Display for Modifier<T> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({:+}", self.bonus)?;
+
+        if self.base_percent != 0.0 {
+            write!(f, " {:+}%", self.base_percent)?;
+        }
+
         if let Some(precision) = f.precision() {
-            write!(f, "(+{}) x {:.*}", self.bonus, precision, self.multiplier)
+            write!(f, ") x {:.*}", precision, self.multiplier)
         } else {
-            write!(f, "(+{}) x {}", self.bonus, self.multiplier)
+            write!(f, ") x {}", self.multiplier)
         }
     }
 }
@@ -0,0 +1,426 @@
+//! Contains the [`FromStr`] implementations for [`Stat`] and [`Modifier`].
+//!
+//! Anything written using [`Display`] can be parsed back,
+//! along with shorter forms such as `+5`, `x1.5`, `-20%` and `10 +5 x1.5`.
+
+use crate::modifier::Modifier;
+use crate::stat::{Override, Stat};
+use crate::value::{Rounding, StatValue};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// An error returned when parsing a [`Stat`] or [`Modifier`] from a string fails.
+#[derive(PartialEq, Debug, Clone)]
+pub struct ParseStatError {
+    position: usize,
+    kind: ParseStatErrorKind,
+}
+
+impl ParseStatError {
+    #[cfg(feature = "fixed_point")]
+    pub(crate) fn new(position: usize, kind: ParseStatErrorKind) -> Self {
+        Self { position, kind }
+    }
+
+    /// The byte offset in the input where parsing failed.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Why parsing failed.
+    pub fn kind(&self) -> &ParseStatErrorKind {
+        &self.kind
+    }
+}
+
+impl Display for ParseStatError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} at position {}", self.kind, self.position)
+    }
+}
+
+impl Error for ParseStatError {}
+
+/// The reason that a [`ParseStatError`] was returned.
+#[derive(PartialEq, Debug, Clone)]
+pub enum ParseStatErrorKind {
+    /// Something else was found where a token was expected, or the input ended early.
+    Expected {
+        /// A description of what was expected, such as `` `)` `` or `a number`.
+        expected: &'static str,
+        /// The character that was found instead, or `None` at the end of the input.
+        found: Option<char>,
+    },
+    /// A number could not be parsed as the stat's value type, or as a multiplier.
+    InvalidNumber(String),
+    /// The name of a [`Rounding`] strategy was not recognized.
+    InvalidRounding(String),
+    /// The same part of a stat was given more than once, such as two multipliers.
+    Duplicate(&'static str),
+    /// The stat can't apply part of a modifier, such as a percentage on a `FixedStat`.
+    Unsupported(&'static str),
+}
+
+impl Display for ParseStatErrorKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseStatErrorKind::Expected { expected, found } => match found {
+                Some(found) => write!(f, "expected {expected}, but found `{found}`"),
+                None => write!(f, "expected {expected}, but found the end of the input"),
+            },
+            ParseStatErrorKind::InvalidNumber(number) => {
+                write!(f, "`{number}` is not a valid number")
+            }
+            ParseStatErrorKind::InvalidRounding(name) => {
+                write!(f, "`{name}` is not a rounding strategy")
+            }
+            ParseStatErrorKind::Duplicate(part) => write!(f, "{part} was given more than once"),
+            ParseStatErrorKind::Unsupported(part) => {
+                write!(f, "{part} is not supported by this stat")
+            }
+        }
+    }
+}
+
+impl<T: StatValue> FromStr for Modifier<T> {
+    type Err = ParseStatError;
+
+    /// Parses a modifier, such as `(+5 +10%) x 2`, `+5 x1.5` or `-20%`.
+    ///
+    /// Each bonus, percentage and multiplier can only be given once, and at least one is required.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser::new(s);
+        let modifier = parser.modifier()?;
+        parser.end()?;
+        Ok(modifier)
+    }
+}
+
+impl<T: StatValue> FromStr for Stat<T> {
+    type Err = ParseStatError;
+
+    /// Parses a stat, such as `(10 + 5) x 2 [0..=100]` or `10 +5 x1.5`.
+    ///
+    /// The base must come first, and is followed by any number of modifier terms.
+    /// Bounds, rounding, persistent modifiers and overrides use the same format as [`Display`].
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser::new(s);
+        let stat = parser.stat()?;
+        parser.end()?;
+        Ok(stat)
+    }
+}
+
+/// The parts of a modifier that have been parsed so far.
+struct Terms<T: StatValue> {
+    bonus: Option<T::Bonus>,
+    base_percent: Option<f32>,
+    multiplier: Option<f32>,
+}
+
+impl<T: StatValue> Terms<T> {
+    fn is_empty(&self) -> bool {
+        self.bonus.is_none() && self.base_percent.is_none() && self.multiplier.is_none()
+    }
+
+    fn into_modifier(self) -> Modifier<T> {
+        Modifier {
+            bonus: self.bonus.unwrap_or_default(),
+            base_percent: self.base_percent.unwrap_or(0.0),
+            multiplier: self.multiplier.unwrap_or(1.0),
+        }
+    }
+}
+
+/// Sets a term, returning an error if it was already set.
+fn set_once<V>(
+    term: &mut Option<V>,
+    value: V,
+    error: ParseStatError,
+) -> Result<(), ParseStatError> {
+    match term {
+        Some(_) => Err(error),
+        None => {
+            *term = Some(value);
+            Ok(())
+        }
+    }
+}
+
+/// A cursor over the input string.
+struct Parser<'a> {
+    input: &'a str,
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(input: &'a str) -> Self {
+        Self { input, position: 0 }
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.input[self.position..]
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.position += rest.len() - rest.trim_start().len();
+    }
+
+    /// Skips whitespace and returns the next character, without consuming it.
+    fn peek(&mut self) -> Option<char> {
+        self.skip_whitespace();
+        self.rest().chars().next()
+    }
+
+    /// Consumes `expected` if it comes next, ignoring leading whitespace.
+    fn eat(&mut self, expected: &str) -> bool {
+        self.skip_whitespace();
+
+        if self.rest().starts_with(expected) {
+            self.position += expected.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(
+        &mut self,
+        expected: &'static str,
+        description: &'static str,
+    ) -> Result<(), ParseStatError> {
+        if self.eat(expected) {
+            Ok(())
+        } else {
+            Err(self.expected(description))
+        }
+    }
+
+    fn expected(&mut self, expected: &'static str) -> ParseStatError {
+        let found = self.peek();
+        self.error(ParseStatErrorKind::Expected { expected, found })
+    }
+
+    fn error(&self, kind: ParseStatErrorKind) -> ParseStatError {
+        ParseStatError {
+            position: self.position,
+            kind,
+        }
+    }
+
+    fn end(&mut self) -> Result<(), ParseStatError> {
+        match self.peek() {
+            None => Ok(()),
+            Some(_) => Err(self.expected("the end of the input")),
+        }
+    }
+
+    /// Consumes the characters of the next number, which may start with a sign.
+    ///
+    /// The token stops before any `x` or `..`, so `5x2` and `0..=10` are split correctly.
+    fn token(&mut self) -> Result<&'a str, ParseStatError> {
+        self.skip_whitespace();
+        let rest = self.rest();
+        let mut previous = None;
+
+        let len = rest
+            .char_indices()
+            .find(|&(i, c)| {
+                let sign =
+                    matches!(c, '+' | '-') && (i == 0 || matches!(previous, Some('e' | 'E')));
+                let part = match c {
+                    '.' => !rest[i..].starts_with(".."),
+                    'x' | 'X' => false,
+                    _ => c.is_alphanumeric() || c == '_',
+                };
+                previous = Some(c);
+                !(sign || part)
+            })
+            .map_or(rest.len(), |(i, _)| i);
+
+        if len == 0 {
+            return Err(self.expected("a number"));
+        }
+
+        self.position += len;
+        Ok(&rest[..len])
+    }
+
+    /// Parses a token that has already been consumed, negating it if it followed a `-`.
+    fn number<N: FromStr>(
+        &self,
+        start: usize,
+        token: &str,
+        negate: bool,
+    ) -> Result<N, ParseStatError> {
+        let text = if negate {
+            format!("-{token}")
+        } else {
+            token.to_string()
+        };
+
+        text.parse().map_err(|_| ParseStatError {
+            position: start,
+            kind: ParseStatErrorKind::InvalidNumber(text),
+        })
+    }
+
+    /// Consumes and parses the next number.
+    fn next_number<N: FromStr>(&mut self) -> Result<N, ParseStatError> {
+        self.skip_whitespace();
+        let start = self.position;
+        let token = self.token()?;
+        self.number(start, token, false)
+    }
+
+    /// Parses bonuses (`+5`), percentages (`-20%`) and multipliers (`x1.5`), in any order.
+    fn terms<T: StatValue>(&mut self, terms: &mut Terms<T>) -> Result<(), ParseStatError> {
+        loop {
+            let start = self.position;
+
+            match self.peek() {
+                Some(sign @ ('+' | '-')) => {
+                    let start = self.position;
+                    self.position += 1;
+                    self.skip_whitespace();
+                    let token_start = self.position;
+                    let token = self.token()?;
+                    let negate = sign == '-';
+
+                    if self.rest().starts_with('%') {
+                        self.position += 1;
+                        let percent = self.number(token_start, token, negate)?;
+                        let error = ParseStatError {
+                            position: start,
+                            kind: ParseStatErrorKind::Duplicate("a percentage"),
+                        };
+                        set_once(&mut terms.base_percent, percent, error)?;
+                    } else {
+                        let bonus = self.number(token_start, token, negate)?;
+                        let error = ParseStatError {
+                            position: start,
+                            kind: ParseStatErrorKind::Duplicate("a bonus"),
+                        };
+                        set_once(&mut terms.bonus, bonus, error)?;
+                    }
+                }
+                Some('x' | 'X' | '*') => {
+                    let start = self.position;
+                    self.position += 1;
+                    let multiplier = self.next_number()?;
+                    let error = ParseStatError {
+                        position: start,
+                        kind: ParseStatErrorKind::Duplicate("a multiplier"),
+                    };
+                    set_once(&mut terms.multiplier, multiplier, error)?;
+                }
+                _ => {
+                    // Don't consume whitespace that belongs to whatever comes next.
+                    self.position = start;
+                    return Ok(());
+                }
+            }
+        }
+    }
+
+    /// Parses a modifier, with the bonus and percentage optionally wrapped in parentheses.
+    fn modifier<T: StatValue>(&mut self) -> Result<Modifier<T>, ParseStatError> {
+        let mut terms = Terms::<T> {
+            bonus: None,
+            base_percent: None,
+            multiplier: None,
+        };
+
+        let parenthesized = self.eat("(");
+        if parenthesized {
+            self.terms(&mut terms)?;
+            self.expect(")", "`)`")?;
+        }
+
+        self.terms(&mut terms)?;
+
+        if !parenthesized && terms.is_empty() {
+            return Err(self.expected("a bonus, percentage or multiplier"));
+        }
+
+        Ok(terms.into_modifier())
+    }
+
+    fn stat<T: StatValue>(&mut self) -> Result<Stat<T>, ParseStatError> {
+        let mut terms = Terms::<T> {
+            bonus: None,
+            base_percent: None,
+            multiplier: None,
+        };
+
+        let parenthesized = self.eat("(");
+        let base = self.next_number()?;
+        self.terms(&mut terms)?;
+        if parenthesized {
+            self.expect(")", "`)`")?;
+        }
+        self.terms(&mut terms)?;
+
+        let modifier = terms.into_modifier();
+        let mut stat = Stat {
+            bonus: modifier.bonus,
+            base_percent: modifier.base_percent,
+            multiplier: modifier.multiplier,
+            ..Stat::new(base)
+        };
+
+        // The remaining parts are optional, but must be in the same order as `Display`.
+        if self.eat("[") {
+            if self.peek() != Some('.') {
+                stat.min = Some(self.next_number()?);
+            }
+            self.expect("..", "`..`")?;
+            if self.eat("=") {
+                stat.max = Some(self.next_number()?);
+            }
+            self.expect("]", "`]`")?;
+        }
+
+        if self.eat("{rounding:") {
+            self.skip_whitespace();
+            let start = self.position;
+            let name = self.token()?;
+            stat.rounding = match name {
+                "Truncate" => Rounding::Truncate,
+                "Floor" => Rounding::Floor,
+                "Ceil" => Rounding::Ceil,
+                "HalfEven" => Rounding::HalfEven,
+                "HalfAway" => Rounding::HalfAway,
+                _ => {
+                    return Err(ParseStatError {
+                        position: start,
+                        kind: ParseStatErrorKind::InvalidRounding(name.to_string()),
+                    });
+                }
+            };
+            self.expect("}", "`}`")?;
+        }
+
+        if self.eat("{persistent:") {
+            stat.persistent = self.modifier()?;
+            self.expect("}", "`}`")?;
+        }
+
+        if self.eat("=") {
+            let value = self.next_number()?;
+            self.expect("(override", "`(override`")?;
+            let priority = if self.eat(",") {
+                self.expect("priority", "`priority`")?;
+                self.next_number()?
+            } else {
+                0
+            };
+            self.expect(")", "`)`")?;
+            stat.overridden = Some(Override::new(value, priority));
+        }
+
+        Ok(stat)
+    }
+}
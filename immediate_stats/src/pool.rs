@@ -0,0 +1,205 @@
+//! Contains a resource pool, such as health or mana, with a maximum stat.
+
+use crate::StatContainer;
+use crate::stat::Stat;
+use crate::value::StatValue;
+use crate::visit::{StatVisitor, StatVisitorMut};
+use std::fmt::{Display, Formatter};
+
+/// How the current value of a [`Pool`] changes when its maximum changes.
+#[derive(PartialEq, Eq, Debug, Default, Copy, Clone, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "bevy",
+    derive(bevy_reflect::Reflect),
+    reflect(PartialEq, Debug, Clone, Hash)
+)]
+pub enum PoolPolicy {
+    /// The current value stays the same, unless it would exceed the new maximum.
+    #[default]
+    KeepCurrent,
+    /// The current value is scaled so that the ratio of current to maximum stays the same.
+    /// For example, a pool at 50/100 will be at 60/120 after its maximum increases by 20%.
+    ///
+    /// The ratio is always calculated from the value that was last set,
+    /// so re-applying the same modifiers every iteration doesn't accumulate rounding errors.
+    KeepRatio,
+}
+
+/// A resource with a current value and a maximum [`Stat`], such as health, mana, or stamina.
+///
+/// Modifiers can be applied to the [maximum](Pool::max) like any other stat.
+/// When the total of the maximum changes, the [current value](Pool::current) is updated using the [`PoolPolicy`].
+/// The current value is always between zero and the total of the maximum,
+/// including after [`reset_modifiers`][reset] is called.
+///
+/// Since the type name doesn't contain "Stat", it must be marked with `#[stat]` when used in a
+/// [derived](macro@crate::StatContainer) container.
+///
+/// [reset]: StatContainer::reset_modifiers
+#[derive(PartialEq, Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "bevy",
+    derive(bevy_reflect::Reflect),
+    reflect(PartialEq, Debug, Clone)
+)]
+pub struct Pool<T: StatValue = i32> {
+    /// The maximum value of the pool.
+    pub max: Stat<T>,
+    /// How the current value changes when the total of `max` changes. This does **not** get reset.
+    pub policy: PoolPolicy,
+    /// The current value when it was last updated, before any changes to the maximum.
+    current: T,
+    /// The total of `max` when `current` was last updated.
+    last_max: T,
+}
+
+impl<T: StatValue> Pool<T> {
+    /// Creates a new full pool from a base maximum.
+    pub fn new(max: T) -> Self {
+        Self::from(Stat::new(max))
+    }
+
+    /// A builder that overwrites the policy used when the maximum changes.
+    pub fn with_policy(mut self, policy: PoolPolicy) -> Self {
+        self.set_current(self.current());
+        self.policy = policy;
+        self
+    }
+
+    /// A builder that overwrites the current value, clamped between zero and the maximum.
+    pub fn with_current(mut self, current: T) -> Self {
+        self.set_current(current);
+        self
+    }
+
+    /// Returns the current value, after applying any changes to the maximum.
+    pub fn current(&self) -> T {
+        let max = self.max.total();
+
+        let current = match self.policy {
+            PoolPolicy::KeepCurrent => self.current,
+            PoolPolicy::KeepRatio if max == self.last_max || self.last_max == T::default() => {
+                self.current
+            }
+            PoolPolicy::KeepRatio => T::from_exact(
+                self.current.to_exact() * max.to_exact() / self.last_max.to_exact(),
+                self.max.rounding,
+            ),
+        };
+
+        clamp_pool(current, max)
+    }
+
+    /// Applies any changes to the maximum since the current value was last updated.
+    ///
+    /// With [`KeepRatio`](PoolPolicy::KeepRatio), the current value is left as it was last set,
+    /// so that it is always rescaled from the original ratio instead of a rounded one.
+    fn sync(&mut self) {
+        if self.policy == PoolPolicy::KeepCurrent {
+            self.set_current(self.current());
+        }
+    }
+
+    /// Overwrites the current value, clamped between zero and the maximum.
+    pub fn set_current(&mut self, current: T) {
+        self.last_max = self.max.total();
+        self.current = clamp_pool(current, self.last_max);
+    }
+
+    /// Returns true if the current value is zero or less.
+    pub fn is_empty(&self) -> bool {
+        self.current() <= T::default()
+    }
+
+    /// Returns true if the current value is equal to the maximum.
+    pub fn is_full(&self) -> bool {
+        self.current() >= self.max.total()
+    }
+
+    /// Sets the current value to the maximum.
+    pub fn fill(&mut self) {
+        self.set_current(self.max.total());
+    }
+
+    /// Removes an amount from the current value, without going below zero.
+    /// Returns the amount that was actually removed.
+    ///
+    /// Negative amounts are treated as zero, so damage can never heal.
+    pub fn damage(&mut self, amount: T) -> T {
+        let current = self.current();
+        let amount = clamp_pool(amount, current);
+
+        self.set_current(current - amount);
+        amount
+    }
+
+    /// Adds an amount to the current value, without going above the maximum.
+    /// Returns the amount that was actually added.
+    ///
+    /// Negative amounts are treated as zero, so healing can never damage.
+    pub fn heal(&mut self, amount: T) -> T {
+        let current = self.current();
+        let missing = self.max.total() - current;
+        let amount = clamp_pool(amount, missing);
+
+        self.set_current(current + amount);
+        amount
+    }
+}
+
+/// Clamps a value between zero and the maximum of a pool.
+fn clamp_pool<T: StatValue>(value: T, max: T) -> T {
+    if value > max {
+        max
+    } else if value < T::default() {
+        T::default()
+    } else {
+        value
+    }
+}
+
+impl<T: StatValue> StatContainer for Pool<T> {
+    fn reset_modifiers(&mut self) {
+        self.sync();
+        self.max.reset_modifiers();
+        self.sync();
+    }
+
+    /// Visits the [maximum](Pool::max) using the pool's path, since the current value is not a stat.
+    fn visit_stats(&self, visitor: &mut StatVisitor<'_>) {
+        self.max.visit_stats(visitor);
+    }
+
+    fn visit_stats_mut(&mut self, visitor: &mut StatVisitorMut<'_>) {
+        self.max.visit_stats_mut(visitor);
+    }
+}
+
+impl<T: StatValue> Default for Pool<T> {
+    fn default() -> Self {
+        Self::from(Stat::default())
+    }
+}
+
+impl<T: StatValue> From<Stat<T>> for Pool<T> {
+    /// Creates a new full pool from a maximum.
+    fn from(max: Stat<T>) -> Self {
+        let current = max.total();
+
+        Self {
+            max,
+            policy: PoolPolicy::default(),
+            current,
+            last_max: current,
+        }
+    }
+}
+
+impl<T: StatValue> Display for Pool<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} / ", self.current())?;
+        self.max.fmt(f)
+    }
+}
@@ -0,0 +1,91 @@
+//! Serializes a [`Stat`] without its temporary modifiers, for use with `#[serde(with = "...")]`.
+//!
+//! Bonuses, multipliers and overrides are reset every iteration, so they usually don't need to be saved.
+//! Everything that survives [`reset_modifiers`](crate::StatContainer::reset_modifiers) is saved,
+//! which is the base, bounds, rounding and [persistent](Stat::persistent) modifiers.
+//! Fields with their default value are omitted.
+//! ```rust
+//! # use immediate_stats::*;
+//! # use serde::{Deserialize, Serialize};
+//! #[derive(StatContainer, Serialize, Deserialize)]
+//! struct Speed {
+//!     #[serde(with = "immediate_stats::skip_temporary")]
+//!     walk: Stat, // Saved as `{ "base": 10 }`.
+//!     run: Stat<f32>, // Saved with all of its fields.
+//! }
+//! ```
+//!
+//! Requires the `serde` feature flag.
+
+use crate::modifier::Modifier;
+use crate::stat::Stat;
+use crate::value::{Rounding, StatValue};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// The fields of a [`Stat`] that are not reset, borrowed for serialization.
+#[derive(Serialize)]
+#[serde(bound = "T: Serialize")]
+struct SavedRef<'a, T: StatValue> {
+    base: &'a T,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    min: &'a Option<T>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max: &'a Option<T>,
+    #[serde(skip_serializing_if = "is_default")]
+    rounding: &'a Rounding,
+    #[serde(skip_serializing_if = "is_default")]
+    persistent: &'a Modifier<T>,
+}
+
+/// The fields of a [`Stat`] that are not reset, with missing fields set to their default value.
+#[derive(Deserialize)]
+#[serde(bound = "T: Deserialize<'de>")]
+struct Saved<T: StatValue> {
+    base: T,
+    #[serde(default)]
+    min: Option<T>,
+    #[serde(default)]
+    max: Option<T>,
+    #[serde(default)]
+    rounding: Rounding,
+    #[serde(default)]
+    persistent: Modifier<T>,
+}
+
+/// Returns true if the value is equal to its default, so that it can be omitted.
+fn is_default<V: Default + PartialEq>(value: &&V) -> bool {
+    **value == V::default()
+}
+
+/// Serializes the stat without its temporary modifiers.
+pub fn serialize<T, S>(stat: &Stat<T>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: StatValue + Serialize,
+    S: Serializer,
+{
+    SavedRef {
+        base: &stat.base,
+        min: &stat.min,
+        max: &stat.max,
+        rounding: &stat.rounding,
+        persistent: &stat.persistent,
+    }
+    .serialize(serializer)
+}
+
+/// Deserializes a stat without temporary modifiers, so it is the same as after being reset.
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Stat<T>, D::Error>
+where
+    T: StatValue + Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    let saved = Saved::<T>::deserialize(deserializer)?;
+
+    Ok(Stat {
+        min: saved.min,
+        max: saved.max,
+        rounding: saved.rounding,
+        persistent: saved.persistent,
+        ..Stat::new(saved.base)
+    })
+}
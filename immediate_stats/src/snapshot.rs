@@ -0,0 +1,143 @@
+//! Contains a stat that remembers its total from the previous iteration.
+
+use crate::StatContainer;
+use crate::modifier::Modifier;
+use crate::stat::Stat;
+use crate::value::StatValue;
+use crate::visit::{StatVisitor, StatVisitorMut};
+use std::fmt::{Display, Formatter};
+use std::ops::{AddAssign, DivAssign, MulAssign, SubAssign};
+
+/// A [`Stat`] that records its total when [`reset_modifiers`][reset] is called,
+/// so that changes can be detected, such as showing an effect when speed drops.
+///
+/// Since modifiers are usually reset at the start of each iteration,
+/// the [previous total](SnapshotStat::previous_total) is the final total of the last iteration.
+/// ```rust
+/// # use immediate_stats::*;
+/// let mut speed = SnapshotStat::new(10);
+///
+/// speed *= 0.5; // Slowed.
+/// assert!(speed.changed());
+/// assert_eq!(speed.delta(), -5.0f64);
+///
+/// speed.reset_modifiers();
+/// speed *= 0.5; // Still slowed.
+/// assert!(!speed.changed());
+/// ```
+///
+/// [reset]: StatContainer::reset_modifiers
+#[derive(PartialEq, Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "bevy",
+    derive(bevy_reflect::Reflect),
+    reflect(PartialEq, Debug, Clone)
+)]
+pub struct SnapshotStat<T: StatValue = i32> {
+    /// The stat that modifiers are applied to.
+    pub stat: Stat<T>,
+    previous: T,
+}
+
+impl<T: StatValue> SnapshotStat<T> {
+    /// Creates a new snapshot stat from a base value.
+    pub fn new(base: T) -> Self {
+        Self::from(Stat::new(base))
+    }
+
+    /// Calculates the total value of the stat. See [`Stat::total`].
+    pub fn total(&self) -> T {
+        self.stat.total()
+    }
+
+    /// Returns the total when modifiers were last reset.
+    ///
+    /// Before the first reset, this is the total when the snapshot stat was created.
+    pub fn previous_total(&self) -> T {
+        self.previous
+    }
+
+    /// Returns true if the current total is different from the [previous total](SnapshotStat::previous_total).
+    pub fn changed(&self) -> bool {
+        self.total() != self.previous
+    }
+
+    /// Returns how much the total has changed since the [previous total](SnapshotStat::previous_total).
+    ///
+    /// This is calculated using the [exact](StatValue::Exact) type, so it can be negative for unsigned stats.
+    pub fn delta(&self) -> T::Exact {
+        self.total().to_exact() - self.previous.to_exact()
+    }
+
+    /// Applies the [`Modifier`] values to the inner stat. See [`Stat::apply`].
+    pub fn apply(&mut self, modifier: Modifier<T>) {
+        self.stat.apply(modifier);
+    }
+}
+
+impl<T: StatValue> StatContainer for SnapshotStat<T> {
+    fn reset_modifiers(&mut self) {
+        self.previous = self.stat.total();
+        self.stat.reset_modifiers();
+    }
+
+    fn visit_stats(&self, visitor: &mut StatVisitor<'_>) {
+        self.stat.visit_stats(visitor);
+    }
+
+    fn visit_stats_mut(&mut self, visitor: &mut StatVisitorMut<'_>) {
+        self.stat.visit_stats_mut(visitor);
+    }
+}
+
+impl<T: StatValue> Default for SnapshotStat<T> {
+    fn default() -> Self {
+        Self::from(Stat::default())
+    }
+}
+
+impl<T: StatValue> From<Stat<T>> for SnapshotStat<T> {
+    fn from(stat: Stat<T>) -> Self {
+        Self {
+            previous: stat.total(),
+            stat,
+        }
+    }
+}
+
+impl<T: StatValue> AddAssign<T> for SnapshotStat<T> {
+    /// Adds to the inner stat's bonus.
+    fn add_assign(&mut self, rhs: T) {
+        self.stat += rhs;
+    }
+}
+
+impl<T: StatValue> SubAssign<T> for SnapshotStat<T> {
+    /// Subtracts from the inner stat's bonus.
+    fn sub_assign(&mut self, rhs: T) {
+        self.stat -= rhs;
+    }
+}
+
+impl<T: StatValue> MulAssign<f32> for SnapshotStat<T> {
+    /// Multiplies the inner stat's multiplier.
+    #[track_caller]
+    fn mul_assign(&mut self, rhs: f32) {
+        self.stat *= rhs;
+    }
+}
+
+impl<T: StatValue> DivAssign<f32> for SnapshotStat<T> {
+    /// Divides the inner stat's multiplier.
+    #[track_caller]
+    fn div_assign(&mut self, rhs: f32) {
+        self.stat /= rhs;
+    }
+}
+
+impl<T: StatValue> Display for SnapshotStat<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.stat.fmt(f)
+    }
+}
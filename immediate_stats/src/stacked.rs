@@ -0,0 +1,225 @@
+//! Contains a stat that limits how many times a modifier can stack.
+
+use crate::StatContainer;
+use crate::modifier::Modifier;
+use crate::stat::Stat;
+use crate::value::StatValue;
+use crate::visit::{StatVisitor, StatVisitorMut};
+use std::fmt::{Display, Formatter};
+use std::ops::{AddAssign, DivAssign, MulAssign, SubAssign};
+
+/// How many times modifiers with the same key can be applied to a [`StackedStat`].
+///
+/// Regardless of the rule, each source can only add one stack per key until the next reset.
+#[derive(PartialEq, Eq, Debug, Copy, Clone, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "bevy",
+    derive(bevy_reflect::Reflect),
+    reflect(PartialEq, Debug, Clone, Hash)
+)]
+pub enum StackingRule {
+    /// Only the first modifier with the key is applied, even if it comes from a different source.
+    /// This is useful for auras, where the same aura from multiple allies should only count once.
+    Unique,
+    /// Up to `n` modifiers with the key are applied, and the rest are ignored.
+    Capped(u32),
+    /// One modifier with the key is applied from every source.
+    Unlimited,
+}
+
+impl StackingRule {
+    /// Returns true if another stack can be added when there are already `count` stacks.
+    fn allows(&self, count: u32) -> bool {
+        match self {
+            StackingRule::Unique => count < 1,
+            StackingRule::Capped(cap) => count < *cap,
+            StackingRule::Unlimited => true,
+        }
+    }
+}
+
+/// The sources of every modifier with the same key that has been applied to a [`StackedStat`].
+#[derive(PartialEq, Eq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "bevy",
+    derive(bevy_reflect::Reflect),
+    reflect(PartialEq, Debug, Clone),
+    reflect(where K: PartialEq + std::fmt::Debug + Clone, S: PartialEq + std::fmt::Debug + Clone)
+)]
+pub struct ModifierStack<K = &'static str, S = &'static str> {
+    /// The key used to identify the stack.
+    pub key: K,
+    /// How many modifiers can be applied with this key.
+    /// This is set by the first modifier applied with the key.
+    pub rule: StackingRule,
+    /// The source of each modifier that has been applied with this key, in the order they were applied.
+    pub sources: Vec<S>,
+}
+
+impl<K, S> ModifierStack<K, S> {
+    /// Returns the number of modifiers that have been applied with this key.
+    pub fn count(&self) -> u32 {
+        self.sources.len() as u32
+    }
+}
+
+/// A [`Stat`] that can have modifiers applied with a stack key, a source, and a [`StackingRule`].
+///
+/// Modifiers that are allowed by their rule are [applied](Stat::apply) to the [inner stat](StackedStat::stat),
+/// while the rest are ignored.
+/// For example, poison with a [`Capped(5)`](StackingRule::Capped) rule will stop stacking after five applications.
+///
+/// Each source can only add one stack per key, so a source that applies the same modifier
+/// twice in one iteration is automatically deduplicated.
+///
+/// Modifiers applied directly to the inner stat, or using operators such as
+/// [`+=`](StackedStat::add_assign), stack like normal.
+/// All stack counts are cleared when [`reset_modifiers`][reset] is called.
+///
+/// [reset]: StatContainer::reset_modifiers
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "bevy",
+    derive(bevy_reflect::Reflect),
+    reflect(PartialEq, Debug, Clone),
+    reflect(where K: PartialEq + std::fmt::Debug + Clone, S: PartialEq + std::fmt::Debug + Clone)
+)]
+pub struct StackedStat<T: StatValue = i32, K = &'static str, S = &'static str> {
+    /// The stat that modifiers are applied to.
+    pub stat: Stat<T>,
+    stacks: Vec<ModifierStack<K, S>>,
+}
+
+impl<T: StatValue, K: PartialEq, S: PartialEq> StackedStat<T, K, S> {
+    /// Creates a new stacked stat from a base value.
+    pub fn new(base: T) -> Self {
+        Self::from(Stat::new(base))
+    }
+
+    /// Calculates the total value of the stat. See [`Stat::total`].
+    pub fn total(&self) -> T {
+        self.stat.total()
+    }
+
+    /// Returns the stack count of every key that has been applied since the last reset.
+    pub fn stacks(&self) -> &[ModifierStack<K, S>] {
+        &self.stacks
+    }
+
+    /// Returns the number of modifiers that have been applied with the given key.
+    pub fn count(&self, key: &K) -> u32 {
+        self.stacks
+            .iter()
+            .find(|stack| &stack.key == key)
+            .map_or(0, ModifierStack::count)
+    }
+
+    /// Applies the [`Modifier`] if the key has not reached its stacking limit,
+    /// and the source has not already applied a modifier with the key.
+    /// Returns true if the modifier was applied.
+    ///
+    /// If this is the first modifier with the key, the given rule is used.
+    /// Otherwise, the key's existing rule is used.
+    pub fn apply_stacking(
+        &mut self,
+        key: K,
+        source: S,
+        rule: StackingRule,
+        modifier: Modifier<T>,
+    ) -> bool {
+        let stack = match self.stacks.iter().position(|stack| stack.key == key) {
+            Some(index) => &mut self.stacks[index],
+            None => {
+                self.stacks.push(ModifierStack {
+                    key,
+                    rule,
+                    sources: Vec::new(),
+                });
+                self.stacks.last_mut().unwrap()
+            }
+        };
+
+        if stack.sources.contains(&source) || !stack.rule.allows(stack.count()) {
+            return false;
+        }
+
+        stack.sources.push(source);
+        self.stat.apply(modifier);
+        true
+    }
+}
+
+impl<T: StatValue, K, S> StatContainer for StackedStat<T, K, S> {
+    fn reset_modifiers(&mut self) {
+        self.stat.reset_modifiers();
+        self.stacks.clear();
+    }
+
+    fn visit_stats(&self, visitor: &mut StatVisitor<'_>) {
+        self.stat.visit_stats(visitor);
+    }
+
+    fn visit_stats_mut(&mut self, visitor: &mut StatVisitorMut<'_>) {
+        self.stat.visit_stats_mut(visitor);
+    }
+}
+
+impl<T: StatValue, K, S> Default for StackedStat<T, K, S> {
+    fn default() -> Self {
+        Self::from(Stat::default())
+    }
+}
+
+impl<T: StatValue, K, S> From<Stat<T>> for StackedStat<T, K, S> {
+    fn from(stat: Stat<T>) -> Self {
+        Self {
+            stat,
+            stacks: Vec::new(),
+        }
+    }
+}
+
+impl<T: StatValue, K, S> AddAssign<T> for StackedStat<T, K, S> {
+    /// Adds to the inner stat's bonus.
+    fn add_assign(&mut self, rhs: T) {
+        self.stat += rhs;
+    }
+}
+
+impl<T: StatValue, K, S> SubAssign<T> for StackedStat<T, K, S> {
+    /// Subtracts from the inner stat's bonus.
+    fn sub_assign(&mut self, rhs: T) {
+        self.stat -= rhs;
+    }
+}
+
+impl<T: StatValue, K, S> MulAssign<f32> for StackedStat<T, K, S> {
+    /// Multiplies the inner stat's multiplier.
+    #[track_caller]
+    fn mul_assign(&mut self, rhs: f32) {
+        self.stat *= rhs;
+    }
+}
+
+impl<T: StatValue, K, S> DivAssign<f32> for StackedStat<T, K, S> {
+    /// Divides the inner stat's multiplier.
+    #[track_caller]
+    fn div_assign(&mut self, rhs: f32) {
+        self.stat /= rhs;
+    }
+}
+
+impl<T: StatValue, K: Display, S> Display for StackedStat<T, K, S> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.stat.fmt(f)?;
+
+        for stack in &self.stacks {
+            write!(f, " {{{} x{}}}", stack.key, stack.count())?;
+        }
+
+        Ok(())
+    }
+}
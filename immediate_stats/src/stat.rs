@@ -1,9 +1,16 @@
 //! Contains the basic stat object.
 
 use crate::StatContainer;
+use crate::easing::EasingCurve;
+use crate::error::{StatError, debug_assert_finite};
+use crate::formula::{FormulaError, ModifierFormula, Variables};
 use crate::modifier::Modifier;
+use crate::parse::ParseStatError;
+use crate::value::{Rounding, StatValue};
+use crate::visit::{AnyModifier, AnyStat, StatVisitor, StatVisitorMut};
 use std::fmt::{Display, Formatter};
 use std::ops::{AddAssign, DivAssign, MulAssign, SubAssign};
+use std::sync::atomic::{self, AtomicU64};
 
 /// A stat that [resets][reset] to a base value every iteration.
 ///
@@ -12,49 +19,230 @@ use std::ops::{AddAssign, DivAssign, MulAssign, SubAssign};
 /// multiplication and division are always applied **after** addition and subtraction.
 /// These modifiers are reset when [`reset_modifiers`][reset] is called.
 ///
+/// The total can optionally be clamped between a [`min`](Stat::min) and [`max`](Stat::max),
+/// which are **not** reset.
+///
+/// An [`Override`] can be used to force the total to a specific value, such as zero speed while stunned.
+///
+/// Modifiers that rarely change, such as equipment, can be [added](Stat::add_persistent) to a
+/// separate [persistent](Stat::persistent) channel, which is **not** reset.
+///
+/// For more complex stacking rules, see [`LayeredStat`](crate::LayeredStat).
+///
+/// The base can be any [`StatValue`], such as `f32` or `u64`, and defaults to `i32`.
+/// The bonus uses the matching [signed type](StatValue::Bonus), so unsigned stats can be debuffed.
+///
 /// [reset]: StatContainer::reset_modifiers
 /// [add]: Stat::add_assign
 /// [sub]: Stat::sub_assign
 /// [mul]: Stat::mul_assign
 /// [div]: Stat::div_assign
-#[derive(PartialEq, Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(
     feature = "bevy",
     derive(bevy_reflect::Reflect),
     reflect(PartialEq, Debug, Clone)
 )]
-pub struct Stat {
+pub struct Stat<T: StatValue = i32> {
     /// The persistent value of the stat.
     /// After being [reset](StatContainer::reset_modifiers), [`Stat::total`] will be equal to `base`.
-    pub base: i32,
+    pub base: T,
     /// Added to `base` during calculation and gets [reset](StatContainer::reset_modifiers) to zero.
     /// This is added **before** `multiplier` is applied.
     ///
+    /// This is [signed](StatValue::Bonus), so unsigned stats can be debuffed.
+    ///
     /// Can be modified using [`+=`](Stat::add_assign) or [`-=`](Stat::sub_assign).
-    pub bonus: i32,
+    pub bonus: T::Bonus,
+    /// A percentage of `base` that is added alongside `bonus`, such as `10.0` for +10% of base.
+    /// Gets [reset](StatContainer::reset_modifiers) to zero.
+    ///
+    /// This is resolved during calculation, so it stays correct if `base` changes.
+    /// Can be modified by [applying](Stat::apply) a [`Modifier`].
+    pub base_percent: f32,
     /// Multiplies the `base` during calculation and gets reset to one every iteration.
     /// This is applied **after** `bonus` is added.
     ///
     /// Can be modified using [`*=`](`Stat::mul_assign`) or [`/=`](`Stat::div_assign`).
     pub multiplier: f32,
+    /// The lower bound of [`Stat::total`]. This does **not** get reset.
+    pub min: Option<T>,
+    /// The upper bound of [`Stat::total`]. This does **not** get reset.
+    pub max: Option<T>,
+    /// How [`Stat::total`] is rounded after the multiplier is applied. This does **not** get reset.
+    pub rounding: Rounding,
+    /// Replaces [`Stat::total`] when present, and gets [reset](StatContainer::reset_modifiers) to `None`.
+    ///
+    /// Can be modified using [`Stat::apply_override`].
+    pub overridden: Option<Override<T>>,
+    /// The sum of all [persistent modifiers](Stat::add_persistent). This does **not** get reset.
+    ///
+    /// During calculation, the persistent bonus is added alongside `bonus`,
+    /// and the persistent multiplier is applied alongside `multiplier`.
+    pub persistent: Modifier<T>,
+    /// Identifies the stat that a [`PersistentHandle`] was created by. This does **not** get reset.
+    ///
+    /// This is zero until a modifier is first [added](Stat::add_persistent) to the persistent channel.
+    /// Copies of the stat share the same ID, since they also contain the modifier.
+    /// This is ignored when comparing stats, since it is not part of the stat's value.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub persistent_id: u64,
+}
+
+/// The next [`Stat::persistent_id`] to be assigned, which is shared by every stat type.
+static NEXT_PERSISTENT_ID: AtomicU64 = AtomicU64::new(1);
+
+/// A handle to a modifier that was [added](Stat::add_persistent) to the persistent channel of a [`Stat`].
+///
+/// This can be passed to [`Stat::remove_persistent`] to remove the modifier.
+/// A handle can only be used with the stat that created it, or a copy of that stat.
+#[must_use = "the modifier can only be removed using its handle"]
+#[derive(PartialEq, Debug)]
+pub struct PersistentHandle<T: StatValue = i32> {
+    stat_id: u64,
+    modifier: Modifier<T>,
 }
 
-impl Stat {
+impl<T: StatValue> PersistentHandle<T> {
+    /// Returns the modifier that the handle refers to.
+    pub fn modifier(&self) -> Modifier<T> {
+        self.modifier
+    }
+}
+
+/// A value that replaces the [total](Stat::total) of a [`Stat`], ignoring all other modifiers and bounds.
+///
+/// If multiple overrides are [applied](Stat::apply_override), the one with the highest priority wins.
+#[derive(PartialEq, Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "bevy",
+    derive(bevy_reflect::Reflect),
+    reflect(PartialEq, Debug, Clone)
+)]
+pub struct Override<T: StatValue = i32> {
+    /// The value that the total is forced to.
+    pub value: T,
+    /// Overrides with a higher priority replace those with a lower priority.
+    /// When priorities are equal, the most recently applied override wins.
+    pub priority: i32,
+}
+
+impl<T: StatValue> Override<T> {
+    /// Creates a new override from a value and a priority.
+    pub fn new(value: T, priority: i32) -> Self {
+        Self { value, priority }
+    }
+}
+
+impl<T: StatValue> Stat<T> {
     /// Creates a new modifier from a base value.
-    pub fn new(base: i32) -> Self {
+    pub fn new(base: T) -> Self {
         Self {
             base,
             ..Default::default()
         }
     }
 
-    /// Calculates the total value of the stat.
-    pub fn total(&self) -> i32 {
-        ((self.base + self.bonus) as f32 * self.multiplier) as i32
+    /// Calculates the total value of the stat, clamped between `min` and `max`.
+    ///
+    /// The total is calculated as `(base + percent of base + persistent bonus + bonus) x persistent multiplier x multiplier`,
+    /// where the percent of base includes both the persistent and immediate base percent.
+    /// The result is rounded using the stat's [`Rounding`] strategy.
+    /// If an [`Override`] is active, its value is returned instead.
+    pub fn total(&self) -> T {
+        if let Some(overridden) = self.overridden {
+            return overridden.value;
+        }
+
+        let total = T::from_exact(
+            self.exact_sum(self.sum()) * T::exact_from_f32(self.combined_multiplier()),
+            self.rounding,
+        );
+        clamp(total, self.min, self.max)
+    }
+
+    /// Calculates the total value of the stat, returning an error instead of overflowing.
+    ///
+    /// Unlike [`Stat::total`], this detects integer overflow and non-finite multipliers.
+    pub fn checked_total(&self) -> Result<T, StatError> {
+        if let Some(overridden) = self.overridden {
+            return Ok(overridden.value);
+        }
+
+        let sum = self
+            .base
+            .to_bonus()
+            .checked_add(self.persistent.bonus)
+            .and_then(|sum| sum.checked_add(self.bonus))
+            .ok_or(StatError::BonusOverflow)?;
+
+        let multiplier = self.combined_multiplier();
+        if !multiplier.is_finite() {
+            return Err(StatError::InvalidMultiplier(multiplier));
+        }
+
+        let total = T::checked_from_exact(
+            self.exact_sum(sum) * T::exact_from_f32(multiplier),
+            self.rounding,
+        )
+        .ok_or(StatError::TotalOverflow)?;
+
+        Ok(clamp(total, self.min, self.max))
+    }
+
+    /// Calculates the total value of the stat, saturating at the bounds of the value type.
+    ///
+    /// For integer stats, a NaN multiplier results in zero.
+    pub fn saturating_total(&self) -> T {
+        if let Some(overridden) = self.overridden {
+            return overridden.value;
+        }
+
+        let sum = self
+            .base
+            .to_bonus()
+            .saturating_add(self.persistent.bonus)
+            .saturating_add(self.bonus);
+
+        let total = T::from_exact(
+            self.exact_sum(sum) * T::exact_from_f32(self.combined_multiplier()),
+            self.rounding,
+        );
+        clamp(total, self.min, self.max)
+    }
+
+    /// Calculates the unrounded total value of the stat, clamped between `min` and `max`.
+    ///
+    /// This is useful for displaying fractional values of integer stats.
+    pub fn total_exact(&self) -> T::Exact {
+        if let Some(overridden) = self.overridden {
+            return overridden.value.to_exact();
+        }
+
+        let total = self.exact_sum(self.sum()) * T::exact_from_f32(self.combined_multiplier());
+        clamp(total, self.min.map(T::to_exact), self.max.map(T::to_exact))
+    }
+
+    /// The base plus the persistent and immediate bonuses.
+    fn sum(&self) -> T::Bonus {
+        self.base.to_bonus() + self.persistent.bonus + self.bonus
+    }
+
+    /// Adds the percent of base to a sum, without rounding.
+    fn exact_sum(&self, sum: T::Bonus) -> T::Exact {
+        let base_percent = self.persistent.base_percent + self.base_percent;
+        T::bonus_to_exact(sum) + self.base.to_exact() * T::exact_from_f32(base_percent / 100.0)
+    }
+
+    /// The product of the persistent and immediate multipliers.
+    fn combined_multiplier(&self) -> f32 {
+        self.persistent.multiplier * self.multiplier
     }
 
     /// A builder that overwrites the current bonus with a new value.
-    pub fn with_bonus(mut self, bonus: i32) -> Self {
+    pub fn with_bonus(mut self, bonus: T::Bonus) -> Self {
         self.bonus = bonus;
         self
     }
@@ -65,88 +253,348 @@ impl Stat {
         self
     }
 
+    /// A builder that overwrites the lower bound of the total.
+    pub fn with_min(mut self, min: T) -> Self {
+        self.min = Some(min);
+        self
+    }
+
+    /// A builder that overwrites the upper bound of the total.
+    pub fn with_max(mut self, max: T) -> Self {
+        self.max = Some(max);
+        self
+    }
+
+    /// A builder that overwrites both the lower and upper bound of the total.
+    pub fn with_bounds(self, min: T, max: T) -> Self {
+        self.with_min(min).with_max(max)
+    }
+
+    /// A builder that overwrites the rounding strategy of the total.
+    pub fn with_rounding(mut self, rounding: Rounding) -> Self {
+        self.rounding = rounding;
+        self
+    }
+
+    /// A builder that overwrites the current override with a new value.
+    pub fn with_override(mut self, overridden: Override<T>) -> Self {
+        self.overridden = Some(overridden);
+        self
+    }
+
     /// A builder that overwrites the current bonus and multiplier with a new value.
-    pub fn with_modifier(mut self, modifier: Modifier) -> Self {
+    pub fn with_modifier(mut self, modifier: Modifier<T>) -> Self {
         self.bonus = modifier.bonus;
+        self.base_percent = modifier.base_percent;
         self.multiplier = modifier.multiplier;
         self
     }
 
-    /// Applies the [`Modifier`] values to the bonus and multiplier.
+    /// A builder that overwrites the persistent modifier with a new value.
     ///
-    /// This adds the bonuses, and multiplies the multipliers.
-    pub fn apply(&mut self, modifier: Modifier) {
+    /// This is useful when loading a stat, but any existing [`PersistentHandle`]s will no longer be valid.
+    pub fn with_persistent(mut self, persistent: Modifier<T>) -> Self {
+        self.persistent = persistent;
+        self.persistent_id = 0;
+        self
+    }
+
+    /// Applies the [`Modifier`] values to the bonus, base percent, and multiplier.
+    ///
+    /// This adds the bonuses and base percents, and multiplies the multipliers.
+    pub fn apply(&mut self, modifier: Modifier<T>) {
         self.bonus += modifier.bonus;
+        self.base_percent += modifier.base_percent;
+        self.multiplier *= modifier.multiplier;
+    }
+
+    /// Adds the [`Modifier`] to the persistent channel, which is **not** reset.
+    ///
+    /// Returns a handle that can be used to [remove](Stat::remove_persistent) the modifier later.
+    ///
+    /// Returns an error if the multiplier is zero or not finite, as it could not be removed.
+    /// If an error is returned, the stat is left unchanged.
+    pub fn add_persistent(
+        &mut self,
+        modifier: Modifier<T>,
+    ) -> Result<PersistentHandle<T>, StatError> {
+        if modifier.multiplier == 0.0 || !modifier.multiplier.is_finite() {
+            return Err(StatError::InvalidPersistentMultiplier(modifier.multiplier));
+        }
+
+        if self.persistent_id == 0 {
+            self.persistent_id = NEXT_PERSISTENT_ID.fetch_add(1, atomic::Ordering::Relaxed);
+        }
+
+        self.persistent.bonus += modifier.bonus;
+        self.persistent.base_percent += modifier.base_percent;
+        self.persistent.multiplier *= modifier.multiplier;
+
+        Ok(PersistentHandle {
+            stat_id: self.persistent_id,
+            modifier,
+        })
+    }
+
+    /// Removes a modifier that was [added](Stat::add_persistent) to the persistent channel.
+    ///
+    /// Multipliers are removed by division, so a small amount of floating point error may remain.
+    ///
+    /// Returns an error if the handle was created by a different stat.
+    /// If an error is returned, the stat is left unchanged.
+    pub fn remove_persistent(&mut self, handle: PersistentHandle<T>) -> Result<(), StatError> {
+        if handle.stat_id != self.persistent_id {
+            return Err(StatError::ForeignHandle);
+        }
+
+        self.persistent.bonus -= handle.modifier.bonus;
+        self.persistent.base_percent -= handle.modifier.base_percent;
+        self.persistent.multiplier /= handle.modifier.multiplier;
+        Ok(())
+    }
+
+    /// Forces the total to a value, unless an override with a higher priority is already active.
+    ///
+    /// When priorities are equal, the new override replaces the old one.
+    pub fn apply_override(&mut self, overridden: Override<T>) {
+        if let Some(current) = self.overridden
+            && current.priority > overridden.priority
+        {
+            return;
+        }
+
+        self.overridden = Some(overridden);
+    }
+
+    /// Applies the [`Modifier`] values to the bonus and multiplier,
+    /// returning an error instead of overflowing.
+    ///
+    /// If an error is returned, the stat is left unchanged.
+    pub fn try_apply(&mut self, modifier: Modifier<T>) -> Result<(), StatError> {
+        let bonus = self
+            .bonus
+            .checked_add(modifier.bonus)
+            .ok_or(StatError::BonusOverflow)?;
+
+        let multiplier = self.multiplier * modifier.multiplier;
+        if !multiplier.is_finite() {
+            return Err(StatError::InvalidMultiplier(multiplier));
+        }
+
+        self.bonus = bonus;
+        self.base_percent += modifier.base_percent;
+        self.multiplier = multiplier;
+        Ok(())
+    }
+
+    /// Applies the [`Modifier`] values to the bonus and multiplier,
+    /// saturating the bonus at the bounds of the value type.
+    pub fn saturating_apply(&mut self, modifier: Modifier<T>) {
+        self.bonus = self.bonus.saturating_add(modifier.bonus);
+        self.base_percent += modifier.base_percent;
         self.multiplier *= modifier.multiplier;
     }
 
     /// [Scales](Modifier::scaled) and applies the [`Modifier`] values to the bonus and multiplier.
     ///
-    /// When the scale is zero, the bonus will be zero while the multiplier will be one.
+    /// When the scale is zero, the bonus and base percent will be zero while the multiplier will be one.
+    /// The scaled bonus is rounded using the stat's [`Rounding`] strategy.
     ///
-    /// This adds the bonuses, and multiplies the multipliers.
-    pub fn apply_scaled(&mut self, modifier: Modifier, fraction: f32) {
-        self.bonus += (modifier.bonus as f32 * fraction) as i32;
+    /// This adds the bonuses and base percents, and multiplies the multipliers.
+    pub fn apply_scaled(&mut self, modifier: Modifier<T>, fraction: f32) {
+        self.bonus += modifier.bonus.scale_rounded(fraction, self.rounding);
+        self.base_percent += modifier.base_percent * fraction;
         // Lerp: https://gist.github.com/laundmo/cb06630109e5e1100f5a2758dfb67cfd
         self.multiplier *= (1.0 - fraction) * 1.0 + fraction * modifier.multiplier;
     }
+
+    /// [Scales](Modifier::scaled_with) and applies the [`Modifier`] values,
+    /// after remapping the fraction with an [easing curve](EasingCurve).
+    ///
+    /// This is the same as [`Stat::apply_scaled`] using the eased fraction.
+    pub fn apply_scaled_with(
+        &mut self,
+        modifier: Modifier<T>,
+        fraction: f32,
+        curve: &impl EasingCurve,
+    ) {
+        self.apply_scaled(modifier, curve.ease(fraction));
+    }
+
+    /// [Evaluates](ModifierFormula::evaluate_rounded) the formula and [applies](Stat::apply) the resulting [`Modifier`].
+    ///
+    /// The bonus is rounded using [`Stat::rounding`].
+    /// If evaluation fails, the stat is left unchanged.
+    pub fn apply_formula(
+        &mut self,
+        formula: &ModifierFormula,
+        variables: &impl Variables,
+    ) -> Result<(), FormulaError> {
+        self.apply(formula.evaluate_rounded(variables, self.rounding)?);
+        Ok(())
+    }
 }
 
-impl StatContainer for Stat {
+impl<T: StatValue> StatContainer for Stat<T> {
     fn reset_modifiers(&mut self) {
-        self.bonus = 0;
+        self.bonus = T::Bonus::default();
+        self.base_percent = 0.0;
         self.multiplier = 1.0;
+        self.overridden = None;
+    }
+
+    fn visit_stats(&self, visitor: &mut StatVisitor<'_>) {
+        visitor.visit(self);
+    }
+
+    fn visit_stats_mut(&mut self, visitor: &mut StatVisitorMut<'_>) {
+        visitor.visit(self);
+    }
+}
+
+impl<T: StatValue> AnyStat for Stat<T> {
+    fn multiply(&mut self, multiplier: f32) {
+        *self *= multiplier;
+    }
+
+    fn parse_modifier(&self, text: &str) -> Result<AnyModifier, ParseStatError> {
+        text.parse::<Modifier<T>>().map(AnyModifier::new)
+    }
+
+    fn apply_modifier(&mut self, modifier: &AnyModifier) -> bool {
+        modifier
+            .downcast_ref::<Modifier<T>>()
+            .map(|modifier| self.apply(*modifier))
+            .is_some()
     }
 }
 
-impl Default for Stat {
+impl<T: StatValue> PartialEq for Stat<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.base == other.base
+            && self.bonus == other.bonus
+            && self.base_percent == other.base_percent
+            && self.multiplier == other.multiplier
+            && self.min == other.min
+            && self.max == other.max
+            && self.rounding == other.rounding
+            && self.overridden == other.overridden
+            && self.persistent == other.persistent
+    }
+}
+
+impl<T: StatValue> Default for Stat<T> {
     fn default() -> Self {
         Self {
-            base: 0,
-            bonus: 0,
+            base: T::default(),
+            bonus: T::Bonus::default(),
+            base_percent: 0.0,
             multiplier: 1.0,
+            min: None,
+            max: None,
+            rounding: Rounding::default(),
+            overridden: None,
+            persistent: Modifier::default(),
+            persistent_id: 0,
         }
     }
 }
 
-impl AddAssign<i32> for Stat {
+/// Clamps a value between an optional lower and upper bound.
+fn clamp<V: PartialOrd>(mut value: V, min: Option<V>, max: Option<V>) -> V {
+    if let Some(min) = min
+        && value < min
+    {
+        value = min;
+    }
+
+    if let Some(max) = max
+        && value > max
+    {
+        value = max;
+    }
+
+    value
+}
+
+impl<T: StatValue> AddAssign<T> for Stat<T> {
     /// Adds to the stat's bonus.
-    fn add_assign(&mut self, rhs: i32) {
-        self.bonus += rhs;
+    fn add_assign(&mut self, rhs: T) {
+        self.bonus += rhs.to_bonus();
     }
 }
 
-impl SubAssign<i32> for Stat {
+impl<T: StatValue> SubAssign<T> for Stat<T> {
     /// Subtracts from the stat's bonus.
-    fn sub_assign(&mut self, rhs: i32) {
-        self.bonus -= rhs;
+    fn sub_assign(&mut self, rhs: T) {
+        self.bonus -= rhs.to_bonus();
     }
 }
 
-impl MulAssign<f32> for Stat {
+impl<T: StatValue> MulAssign<f32> for Stat<T> {
     /// Multiplies the stat's multiplier.
+    ///
+    /// In debug builds, this panics if the resulting multiplier is NaN or infinite.
+    #[track_caller]
     fn mul_assign(&mut self, rhs: f32) {
         self.multiplier *= rhs;
+        debug_assert_finite(self.multiplier);
     }
 }
 
-impl DivAssign<f32> for Stat {
+impl<T: StatValue> DivAssign<f32> for Stat<T> {
     /// Divides the stat's multiplier.
+    ///
+    /// In debug builds, this panics if the resulting multiplier is NaN or infinite.
+    #[track_caller]
     fn div_assign(&mut self, rhs: f32) {
         self.multiplier /= rhs;
+        debug_assert_finite(self.multiplier);
     }
 }
 
-impl Display for Stat {
+impl<T: StatValue> Display for Stat<T> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({} + {}", self.base, self.bonus)?;
+
+        if self.base_percent != 0.0 {
+            write!(f, " + {}%", self.base_percent)?;
+        }
+
         if let Some(precision) = f.precision() {
-            write!(
-                f,
-                "({} + {}) x {:.*}",
-                self.base, self.bonus, precision, self.multiplier
-            )
+            write!(f, ") x {:.*}", precision, self.multiplier)
         } else {
-            write!(f, "({} + {}) x {}", self.base, self.bonus, self.multiplier)
+            write!(f, ") x {}", self.multiplier)
+        }?;
+
+        // Bounds are written as a range, such as `[0..=100]`.
+        match (self.min, self.max) {
+            (None, None) => Ok(()),
+            (Some(min), None) => write!(f, " [{min}..]"),
+            (None, Some(max)) => write!(f, " [..={max}]"),
+            (Some(min), Some(max)) => write!(f, " [{min}..={max}]"),
+        }?;
+
+        if self.rounding != Rounding::default() {
+            write!(f, " {{rounding: {:?}}}", self.rounding)?;
         }
+
+        if self.persistent != Modifier::default() {
+            write!(f, " {{persistent: ")?;
+            self.persistent.fmt(f)?;
+            write!(f, "}}")?;
+        }
+
+        match self.overridden {
+            Some(overridden) if overridden.priority != 0 => write!(
+                f,
+                " = {} (override, priority {})",
+                overridden.value, overridden.priority
+            ),
+            Some(overridden) => write!(f, " = {} (override)", overridden.value),
+            None => Ok(()),
+        }?;
+
+        Ok(())
     }
 }
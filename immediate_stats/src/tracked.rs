@@ -0,0 +1,223 @@
+//! Contains a stat that records where its modifiers came from.
+
+use crate::StatContainer;
+use crate::modifier::Modifier;
+use crate::parse::ParseStatError;
+use crate::stat::Stat;
+use crate::value::StatValue;
+use crate::visit::{AnyModifier, AnyStat, StatVisitor, StatVisitorMut};
+use std::fmt::{Display, Formatter};
+use std::ops::{AddAssign, DivAssign, MulAssign, SubAssign};
+
+/// A [`Modifier`] that was applied to a [`TrackedStat`], along with an optional source label.
+#[derive(PartialEq, Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "bevy",
+    derive(bevy_reflect::Reflect),
+    reflect(PartialEq, Debug, Clone),
+    reflect(where S: PartialEq + std::fmt::Debug + Clone)
+)]
+pub struct Contribution<T: StatValue = i32, S = &'static str> {
+    /// The label or ID of whatever applied the modifier.
+    /// This is `None` when applied using operators such as [`+=`](TrackedStat::add_assign).
+    pub source: Option<S>,
+    /// The modifier that was applied.
+    pub modifier: Modifier<T>,
+}
+
+/// A [`Stat`] that records every modifier applied to it, for use in tooltips and debugging.
+///
+/// Modifiers can be applied with a source using [`TrackedStat::apply`], [`TrackedStat::add`],
+/// and [`TrackedStat::multiply`], or without one using the usual operators.
+/// The recorded [contributions](Contribution) are cleared when [`reset_modifiers`][reset] is called.
+///
+/// Unlike [`Stat`], this is not `Copy`, so only use it for stats that need to be inspected.
+///
+/// [reset]: StatContainer::reset_modifiers
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "bevy",
+    derive(bevy_reflect::Reflect),
+    reflect(PartialEq, Debug, Clone),
+    reflect(where S: PartialEq + std::fmt::Debug + Clone)
+)]
+pub struct TrackedStat<T: StatValue = i32, S = &'static str> {
+    stat: Stat<T>,
+    contributions: Vec<Contribution<T, S>>,
+}
+
+impl<T: StatValue, S> TrackedStat<T, S> {
+    /// Creates a new tracked stat. Any modifiers already applied to the stat are reset.
+    pub fn new(mut stat: Stat<T>) -> Self {
+        stat.reset_modifiers();
+
+        Self {
+            stat,
+            contributions: Vec::new(),
+        }
+    }
+
+    /// Returns the underlying stat, including all applied modifiers.
+    pub fn stat(&self) -> &Stat<T> {
+        &self.stat
+    }
+
+    /// Overwrites the base value of the stat.
+    pub fn set_base(&mut self, base: T) {
+        self.stat.base = base;
+    }
+
+    /// Calculates the total value of the stat. See [`Stat::total`].
+    pub fn total(&self) -> T {
+        self.stat.total()
+    }
+
+    /// Returns every modifier applied since the last reset, in the order they were applied.
+    pub fn contributions(&self) -> &[Contribution<T, S>] {
+        &self.contributions
+    }
+
+    /// Returns every modifier applied since the last reset, along with its effect on the total.
+    ///
+    /// The effect is the [exact](Stat::total_exact) amount that the total would decrease by
+    /// if that contribution was removed.
+    pub fn breakdown(&self) -> impl Iterator<Item = (&Contribution<T, S>, T::Exact)> {
+        let total = self.stat.total_exact();
+
+        self.contributions
+            .iter()
+            .enumerate()
+            .map(move |(index, contribution)| (contribution, total - self.total_without(index)))
+    }
+
+    /// Calculates the exact total as if the contribution at `index` was never applied.
+    fn total_without(&self, index: usize) -> T::Exact {
+        let mut stat = self.stat;
+        stat.reset_modifiers();
+
+        for (_, contribution) in self
+            .contributions
+            .iter()
+            .enumerate()
+            .filter(|(other, _)| *other != index)
+        {
+            stat.apply(contribution.modifier);
+        }
+
+        stat.total_exact()
+    }
+
+    /// Applies the [`Modifier`] to the stat, and records it with a source.
+    pub fn apply(&mut self, source: S, modifier: Modifier<T>) {
+        self.record(Some(source), modifier);
+    }
+
+    /// Adds to the stat's bonus, and records it with a source.
+    pub fn add(&mut self, source: S, bonus: T) {
+        self.record(Some(source), Modifier::from_bonus(bonus.to_bonus()));
+    }
+
+    /// Multiplies the stat's multiplier, and records it with a source.
+    pub fn multiply(&mut self, source: S, multiplier: f32) {
+        self.record(Some(source), Modifier::from_multiplier(multiplier));
+    }
+
+    /// Applies the [`Modifier`] to the stat, and records it.
+    fn record(&mut self, source: Option<S>, modifier: Modifier<T>) {
+        self.stat.apply(modifier);
+        self.contributions.push(Contribution { source, modifier });
+    }
+}
+
+impl<T: StatValue, S: std::fmt::Debug + 'static> StatContainer for TrackedStat<T, S> {
+    fn reset_modifiers(&mut self) {
+        self.stat.reset_modifiers();
+        self.contributions.clear();
+    }
+
+    fn visit_stats(&self, visitor: &mut StatVisitor<'_>) {
+        visitor.visit(self);
+    }
+
+    fn visit_stats_mut(&mut self, visitor: &mut StatVisitorMut<'_>) {
+        visitor.visit(self);
+    }
+}
+
+impl<T: StatValue, S: std::fmt::Debug + 'static> AnyStat for TrackedStat<T, S> {
+    /// Multiplies the stat's multiplier, and records it without a source.
+    fn multiply(&mut self, multiplier: f32) {
+        *self *= multiplier;
+    }
+
+    fn parse_modifier(&self, text: &str) -> Result<AnyModifier, ParseStatError> {
+        text.parse::<Modifier<T>>().map(AnyModifier::new)
+    }
+
+    /// Applies the modifier, and records it without a source.
+    fn apply_modifier(&mut self, modifier: &AnyModifier) -> bool {
+        modifier
+            .downcast_ref::<Modifier<T>>()
+            .map(|modifier| self.record(None, *modifier))
+            .is_some()
+    }
+}
+
+impl<T: StatValue, S> Default for TrackedStat<T, S> {
+    fn default() -> Self {
+        Self::new(Stat::default())
+    }
+}
+
+impl<T: StatValue, S> From<Stat<T>> for TrackedStat<T, S> {
+    fn from(stat: Stat<T>) -> Self {
+        Self::new(stat)
+    }
+}
+
+impl<T: StatValue, S> AddAssign<T> for TrackedStat<T, S> {
+    /// Adds to the stat's bonus, and records it without a source.
+    fn add_assign(&mut self, rhs: T) {
+        self.record(None, Modifier::from_bonus(rhs.to_bonus()));
+    }
+}
+
+impl<T: StatValue, S> SubAssign<T> for TrackedStat<T, S> {
+    /// Subtracts from the stat's bonus, and records it as a negative bonus without a source.
+    fn sub_assign(&mut self, rhs: T) {
+        self.record(None, Modifier::from_bonus(-rhs.to_bonus()));
+    }
+}
+
+impl<T: StatValue, S> MulAssign<f32> for TrackedStat<T, S> {
+    /// Multiplies the stat's multiplier, and records it without a source.
+    fn mul_assign(&mut self, rhs: f32) {
+        self.record(None, Modifier::from_multiplier(rhs));
+    }
+}
+
+impl<T: StatValue, S> DivAssign<f32> for TrackedStat<T, S> {
+    /// Divides the stat's multiplier, and records it without a source.
+    fn div_assign(&mut self, rhs: f32) {
+        self.record(None, Modifier::from_multiplier(1.0 / rhs));
+    }
+}
+
+impl<T: StatValue, S> Display for TrackedStat<T, S> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.stat.fmt(f)
+    }
+}
+
+impl<T: StatValue, S: Display> Display for Contribution<T, S> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.source {
+            Some(source) => write!(f, "{source}: ")?,
+            None => write!(f, "unknown: ")?,
+        }
+
+        self.modifier.fmt(f)
+    }
+}
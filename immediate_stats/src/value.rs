@@ -0,0 +1,314 @@
+//! Contains the numeric trait used by [`Stat`](crate::Stat) and [`Modifier`](crate::Modifier).
+
+use std::fmt::{Debug, Display};
+use std::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+/// A numeric type that can be used as the base and bonus of a [`Stat`](crate::Stat).
+///
+/// This is implemented for all primitive integer and float types.
+/// Multipliers are always stored as an `f32`, and are applied using [`StatValue::scale`].
+pub trait StatValue:
+    'static
+    + Send
+    + Sync
+    + Copy
+    + Default
+    + PartialEq
+    + PartialOrd
+    + Debug
+    + Display
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + AddAssign
+    + SubAssign
+    + FromStr
+{
+    /// The float type used to calculate unrounded values.
+    ///
+    /// This is `f64` for 32-bit and larger types, so that every `i32` and `u32` can be represented exactly,
+    /// and `f32` for everything else.
+    type Exact: Copy
+        + PartialOrd
+        + Debug
+        + Display
+        + From<f32>
+        + Add<Output = Self::Exact>
+        + Sub<Output = Self::Exact>
+        + Mul<Output = Self::Exact>
+        + Div<Output = Self::Exact>;
+
+    /// The signed type used for bonuses, so that unsigned stats can be debuffed.
+    ///
+    /// This is the next larger signed integer for unsigned integers, such as `i64` for `u32`,
+    /// and the type itself for everything else.
+    /// Since there is no signed integer larger than `i128`, `u128` bonuses saturate at `i128::MAX`.
+    type Bonus: StatValue + Neg<Output = Self::Bonus> + SerdeBonus;
+
+    /// Converts the value into its [exact](StatValue::Exact) representation.
+    fn to_exact(self) -> Self::Exact;
+
+    /// Converts a multiplier or fraction into the [exact](StatValue::Exact) representation.
+    ///
+    /// When the exact type is `f64`, this uses the shortest decimal that represents the `f32`,
+    /// so that `x1.3` multiplies by `1.3` rather than `1.2999999523`.
+    fn exact_from_f32(value: f32) -> Self::Exact;
+
+    /// Converts an `f64` into the [exact](StatValue::Exact) representation,
+    /// without losing precision when the exact type is also `f64`.
+    fn exact_from_f64(value: f64) -> Self::Exact;
+
+    /// Converts the value into a [bonus](StatValue::Bonus), saturating if it does not fit.
+    fn to_bonus(self) -> Self::Bonus;
+
+    /// Converts a [bonus](StatValue::Bonus) back, saturating at the bounds of the type.
+    fn from_bonus(bonus: Self::Bonus) -> Self;
+
+    /// Converts a [bonus](StatValue::Bonus) into this type's [exact](StatValue::Exact) representation.
+    fn bonus_to_exact(bonus: Self::Bonus) -> Self::Exact;
+
+    /// Converts an [exact](StatValue::Exact) value back, using the given rounding strategy.
+    ///
+    /// Floats are never rounded. For integers, the result saturates at the bounds of the type.
+    fn from_exact(exact: Self::Exact, rounding: Rounding) -> Self;
+
+    /// Converts an [exact](StatValue::Exact) value back, using the given rounding strategy.
+    ///
+    /// Returns `None` if the rounded value is not finite or does not fit in the type.
+    fn checked_from_exact(exact: Self::Exact, rounding: Rounding) -> Option<Self>;
+
+    /// Adds two values, returning `None` if the result overflows.
+    ///
+    /// For floats, this returns `None` if the result is not finite.
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+
+    /// Adds two values, saturating at the bounds of the type instead of overflowing.
+    fn saturating_add(self, rhs: Self) -> Self;
+
+    /// Multiplies the value by a fraction, truncating the result toward zero.
+    fn scale(self, fraction: f32) -> Self {
+        self.scale_rounded(fraction, Rounding::Truncate)
+    }
+
+    /// Multiplies the value by a fraction, using the given rounding strategy.
+    fn scale_rounded(self, fraction: f32, rounding: Rounding) -> Self {
+        Self::from_exact(self.to_exact() * Self::exact_from_f32(fraction), rounding)
+    }
+}
+
+/// Requires [bonuses](StatValue::Bonus) to be serializable when the `serde` feature is enabled,
+/// so that stats can derive `Serialize` and `Deserialize` without extra bounds.
+#[cfg(feature = "serde")]
+pub trait SerdeBonus: serde::Serialize + serde::de::DeserializeOwned {}
+
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize + serde::de::DeserializeOwned> SerdeBonus for T {}
+
+/// Requires [bonuses](StatValue::Bonus) to be serializable when the `serde` feature is enabled,
+/// so that stats can derive `Serialize` and `Deserialize` without extra bounds.
+#[cfg(not(feature = "serde"))]
+pub trait SerdeBonus {}
+
+#[cfg(not(feature = "serde"))]
+impl<T> SerdeBonus for T {}
+
+/// How an integer [`Stat`](crate::Stat) rounds its total after multipliers are applied.
+///
+/// Has no effect on float stats.
+#[derive(PartialEq, Eq, Debug, Copy, Clone, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "bevy",
+    derive(bevy_reflect::Reflect),
+    reflect(PartialEq, Debug, Clone, Hash)
+)]
+pub enum Rounding {
+    /// Rounds toward zero, so `2.7` becomes `2` and `-2.7` becomes `-2`.
+    #[default]
+    Truncate,
+    /// Rounds toward negative infinity, so `2.7` becomes `2` and `-2.7` becomes `-3`.
+    Floor,
+    /// Rounds toward positive infinity, so `2.2` becomes `3` and `-2.7` becomes `-2`.
+    Ceil,
+    /// Rounds to the nearest integer, with halfway cases rounded to the nearest even integer.
+    /// So `2.5` becomes `2` and `3.5` becomes `4`.
+    HalfEven,
+    /// Rounds to the nearest integer, with halfway cases rounded away from zero.
+    /// So `2.5` becomes `3` and `-2.5` becomes `-3`.
+    HalfAway,
+}
+
+/// Floats that can be rounded using a [`Rounding`] strategy.
+trait Round {
+    fn round_with(self, rounding: Rounding) -> Self;
+}
+
+/// Implements [`Round`] for floats.
+macro_rules! impl_round {
+    ($($float:ty),*) => {
+        $(
+            impl Round for $float {
+                fn round_with(self, rounding: Rounding) -> Self {
+                    match rounding {
+                        Rounding::Truncate => self.trunc(),
+                        Rounding::Floor => self.floor(),
+                        Rounding::Ceil => self.ceil(),
+                        Rounding::HalfEven => self.round_ties_even(),
+                        Rounding::HalfAway => self.round(),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+/// Floats that an `f32` can be converted into without gaining extra decimal digits.
+trait FromDecimal {
+    fn from_decimal(value: f32) -> Self;
+}
+
+impl FromDecimal for f32 {
+    fn from_decimal(value: f32) -> Self {
+        value
+    }
+}
+
+impl FromDecimal for f64 {
+    /// Finds the `f64` closest to the shortest decimal that rounds back to `value`.
+    fn from_decimal(value: f32) -> Self {
+        let wide = f64::from(value);
+
+        if value == 0.0 || !value.is_finite() {
+            return wide;
+        }
+
+        // An `f32` never needs more than 9 significant digits.
+        let magnitude = wide.abs().log10().floor() as i32;
+        for digits in 1..=9 {
+            let scale = 10f64.powi(digits - 1 - magnitude);
+            let decimal = (wide * scale).round() / scale;
+
+            if decimal as f32 == value {
+                return decimal;
+            }
+        }
+
+        wide
+    }
+}
+
+/// Implements [`StatValue`] for integers, using the given float type as the exact representation,
+/// and the type after each integer as its [bonus](StatValue::Bonus).
+macro_rules! impl_stat_value_int {
+    ($float:ty => $($ty:ty: $bonus:ty),*) => {
+        $(
+            impl StatValue for $ty {
+                type Exact = $float;
+                type Bonus = $bonus;
+
+                fn to_exact(self) -> $float {
+                    self as $float
+                }
+
+                fn exact_from_f32(value: f32) -> $float {
+                    <$float>::from_decimal(value)
+                }
+
+                fn exact_from_f64(value: f64) -> $float {
+                    value as $float
+                }
+
+                fn to_bonus(self) -> $bonus {
+                    <$bonus>::try_from(self).unwrap_or(<$bonus>::MAX)
+                }
+
+                fn from_bonus(bonus: $bonus) -> Self {
+                    let saturated = if bonus < 0 { <$ty>::MIN } else { <$ty>::MAX };
+                    <$ty>::try_from(bonus).unwrap_or(saturated)
+                }
+
+                fn bonus_to_exact(bonus: $bonus) -> $float {
+                    bonus as $float
+                }
+
+                fn from_exact(exact: $float, rounding: Rounding) -> Self {
+                    exact.round_with(rounding) as $ty
+                }
+
+                fn checked_from_exact(exact: $float, rounding: Rounding) -> Option<Self> {
+                    let rounded = exact.round_with(rounding);
+
+                    // `MAX` may not be representable as a float, so compare against `MAX + 1` instead.
+                    (rounded >= <$ty>::MIN as $float && rounded < <$ty>::MAX as $float + 1.0)
+                        .then_some(rounded as $ty)
+                }
+
+                fn checked_add(self, rhs: Self) -> Option<Self> {
+                    <$ty>::checked_add(self, rhs)
+                }
+
+                fn saturating_add(self, rhs: Self) -> Self {
+                    <$ty>::saturating_add(self, rhs)
+                }
+            }
+        )*
+    };
+}
+
+/// Implements [`StatValue`] for floats, which are their own exact representation.
+macro_rules! impl_stat_value_float {
+    ($($ty:ty),*) => {
+        $(
+            impl StatValue for $ty {
+                type Exact = $ty;
+                type Bonus = $ty;
+
+                fn to_exact(self) -> $ty {
+                    self
+                }
+
+                fn exact_from_f32(value: f32) -> $ty {
+                    <$ty>::from_decimal(value)
+                }
+
+                fn exact_from_f64(value: f64) -> $ty {
+                    value as $ty
+                }
+
+                fn to_bonus(self) -> $ty {
+                    self
+                }
+
+                fn from_bonus(bonus: $ty) -> Self {
+                    bonus
+                }
+
+                fn bonus_to_exact(bonus: $ty) -> $ty {
+                    bonus
+                }
+
+                fn from_exact(exact: $ty, _rounding: Rounding) -> Self {
+                    exact
+                }
+
+                fn checked_from_exact(exact: $ty, _rounding: Rounding) -> Option<Self> {
+                    exact.is_finite().then_some(exact)
+                }
+
+                fn checked_add(self, rhs: Self) -> Option<Self> {
+                    let sum = self + rhs;
+                    sum.is_finite().then_some(sum)
+                }
+
+                fn saturating_add(self, rhs: Self) -> Self {
+                    (self + rhs).clamp(<$ty>::MIN, <$ty>::MAX)
+                }
+            }
+        )*
+    };
+}
+
+impl_round!(f32, f64);
+impl_stat_value_int!(f32 => i8: i8, i16: i16, u8: i16, u16: i32);
+impl_stat_value_int!(f64 => i32: i32, u32: i64, i64: i64, i128: i128, isize: isize, u64: i128, u128: i128, usize: i128);
+impl_stat_value_float!(f32, f64);
@@ -0,0 +1,282 @@
+//! Contains a stat with a separate value for each axis of a vector.
+
+use crate::StatContainer;
+use crate::easing::EasingCurve;
+use crate::modifier::Modifier;
+use crate::stat::Stat;
+use crate::value::StatValue;
+use crate::visit::{StatVisitor, StatVisitorMut};
+use std::fmt::{Display, Formatter};
+use std::ops::{AddAssign, DivAssign, Index, IndexMut, MulAssign, SubAssign};
+
+/// A two-dimensional [`VectorStat`].
+pub type Vec2Stat<T = f32> = VectorStat<T, 2>;
+
+/// A three-dimensional [`VectorStat`].
+pub type Vec3Stat<T = f32> = VectorStat<T, 3>;
+
+/// A stat with a separate [`Stat`] for each axis, such as per-axis knockback resistance.
+///
+/// Scalar modifiers, such as [`+= 5.0`][add] or [`*= 2.0`][mul], are applied to every axis,
+/// while arrays, such as `+= [5.0, 0.0, 0.0]`, are applied component-wise.
+/// All axes are reset when [`reset_modifiers`][reset] is called.
+///
+/// The result can be converted into a vector type using the array returned by [`VectorStat::total`],
+/// such as `Vec3::from_array(stat.total())`.
+///
+/// [reset]: StatContainer::reset_modifiers
+/// [add]: VectorStat::add_assign
+/// [mul]: VectorStat::mul_assign
+#[derive(PartialEq, Debug, Copy, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(into = "VectorStatAxes<T>", try_from = "VectorStatAxes<T>")
+)]
+#[cfg_attr(
+    feature = "bevy",
+    derive(bevy_reflect::Reflect),
+    reflect(PartialEq, Debug, Clone)
+)]
+pub struct VectorStat<T: StatValue = f32, const N: usize = 3> {
+    /// The stat of each axis.
+    pub axes: [Stat<T>; N],
+}
+
+impl<T: StatValue, const N: usize> VectorStat<T, N> {
+    /// Creates a new vector stat from the base value of each axis.
+    pub fn new(base: [T; N]) -> Self {
+        Self {
+            axes: base.map(Stat::new),
+        }
+    }
+
+    /// Creates a new vector stat with the same base value on every axis.
+    pub fn splat(base: T) -> Self {
+        Self::new([base; N])
+    }
+
+    /// Returns the base value of each axis.
+    pub fn base(&self) -> [T; N] {
+        self.axes.map(|axis| axis.base)
+    }
+
+    /// Calculates the total value of each axis. See [`Stat::total`].
+    pub fn total(&self) -> [T; N] {
+        self.axes.map(|axis| axis.total())
+    }
+
+    /// Applies the [`Modifier`] values to every axis.
+    pub fn apply(&mut self, modifier: Modifier<T>) {
+        for axis in &mut self.axes {
+            axis.apply(modifier);
+        }
+    }
+
+    /// Applies each [`Modifier`] to its corresponding axis.
+    pub fn apply_each(&mut self, modifiers: [Modifier<T>; N]) {
+        for (axis, modifier) in self.axes.iter_mut().zip(modifiers) {
+            axis.apply(modifier);
+        }
+    }
+
+    /// [Scales](Modifier::scaled) and applies the [`Modifier`] values to every axis.
+    /// See [`Stat::apply_scaled`].
+    pub fn apply_scaled(&mut self, modifier: Modifier<T>, fraction: f32) {
+        for axis in &mut self.axes {
+            axis.apply_scaled(modifier, fraction);
+        }
+    }
+
+    /// [Scales](Modifier::scaled_with) and applies the [`Modifier`] values to every axis,
+    /// after remapping the fraction with an [easing curve](EasingCurve).
+    pub fn apply_scaled_with(
+        &mut self,
+        modifier: Modifier<T>,
+        fraction: f32,
+        curve: &impl EasingCurve,
+    ) {
+        self.apply_scaled(modifier, curve.ease(fraction));
+    }
+}
+
+/// The serialized form of a [`VectorStat`], since serde only supports arrays of up to 32 elements.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct VectorStatAxes<T: StatValue> {
+    axes: Vec<Stat<T>>,
+}
+
+#[cfg(feature = "serde")]
+impl<T: StatValue, const N: usize> From<VectorStat<T, N>> for VectorStatAxes<T> {
+    fn from(stat: VectorStat<T, N>) -> Self {
+        Self {
+            axes: stat.axes.to_vec(),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: StatValue, const N: usize> TryFrom<VectorStatAxes<T>> for VectorStat<T, N> {
+    type Error = String;
+
+    fn try_from(value: VectorStatAxes<T>) -> Result<Self, Self::Error> {
+        let len = value.axes.len();
+
+        let axes = value
+            .axes
+            .try_into()
+            .map_err(|_| format!("expected {N} axes, but found {len}"))?;
+
+        Ok(Self { axes })
+    }
+}
+
+impl<T: StatValue, const N: usize> StatContainer for VectorStat<T, N> {
+    fn reset_modifiers(&mut self) {
+        for axis in &mut self.axes {
+            axis.reset_modifiers();
+        }
+    }
+
+    /// Visits each axis, using its index as the name.
+    fn visit_stats(&self, visitor: &mut StatVisitor<'_>) {
+        for (index, axis) in self.axes.iter().enumerate() {
+            visitor.field(&index.to_string(), axis);
+        }
+    }
+
+    fn visit_stats_mut(&mut self, visitor: &mut StatVisitorMut<'_>) {
+        for (index, axis) in self.axes.iter_mut().enumerate() {
+            visitor.field(&index.to_string(), axis);
+        }
+    }
+}
+
+impl<T: StatValue, const N: usize> Default for VectorStat<T, N> {
+    fn default() -> Self {
+        Self {
+            axes: [Stat::default(); N],
+        }
+    }
+}
+
+impl<T: StatValue, const N: usize> From<[T; N]> for VectorStat<T, N> {
+    fn from(base: [T; N]) -> Self {
+        Self::new(base)
+    }
+}
+
+impl<T: StatValue, const N: usize> Index<usize> for VectorStat<T, N> {
+    type Output = Stat<T>;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.axes[index]
+    }
+}
+
+impl<T: StatValue, const N: usize> IndexMut<usize> for VectorStat<T, N> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.axes[index]
+    }
+}
+
+impl<T: StatValue, const N: usize> AddAssign<T> for VectorStat<T, N> {
+    /// Adds to the bonus of every axis.
+    fn add_assign(&mut self, rhs: T) {
+        for axis in &mut self.axes {
+            *axis += rhs;
+        }
+    }
+}
+
+impl<T: StatValue, const N: usize> AddAssign<[T; N]> for VectorStat<T, N> {
+    /// Adds to the bonus of each axis, component-wise.
+    fn add_assign(&mut self, rhs: [T; N]) {
+        for (axis, rhs) in self.axes.iter_mut().zip(rhs) {
+            *axis += rhs;
+        }
+    }
+}
+
+impl<T: StatValue, const N: usize> SubAssign<T> for VectorStat<T, N> {
+    /// Subtracts from the bonus of every axis.
+    fn sub_assign(&mut self, rhs: T) {
+        for axis in &mut self.axes {
+            *axis -= rhs;
+        }
+    }
+}
+
+impl<T: StatValue, const N: usize> SubAssign<[T; N]> for VectorStat<T, N> {
+    /// Subtracts from the bonus of each axis, component-wise.
+    fn sub_assign(&mut self, rhs: [T; N]) {
+        for (axis, rhs) in self.axes.iter_mut().zip(rhs) {
+            *axis -= rhs;
+        }
+    }
+}
+
+impl<T: StatValue, const N: usize> MulAssign<f32> for VectorStat<T, N> {
+    /// Multiplies the multiplier of every axis.
+    ///
+    /// In debug builds, this panics if a resulting multiplier is NaN or infinite.
+    #[track_caller]
+    fn mul_assign(&mut self, rhs: f32) {
+        for axis in &mut self.axes {
+            *axis *= rhs;
+        }
+    }
+}
+
+impl<T: StatValue, const N: usize> MulAssign<[f32; N]> for VectorStat<T, N> {
+    /// Multiplies the multiplier of each axis, component-wise.
+    ///
+    /// In debug builds, this panics if a resulting multiplier is NaN or infinite.
+    #[track_caller]
+    fn mul_assign(&mut self, rhs: [f32; N]) {
+        for (axis, rhs) in self.axes.iter_mut().zip(rhs) {
+            *axis *= rhs;
+        }
+    }
+}
+
+impl<T: StatValue, const N: usize> DivAssign<f32> for VectorStat<T, N> {
+    /// Divides the multiplier of every axis.
+    ///
+    /// In debug builds, this panics if a resulting multiplier is NaN or infinite.
+    #[track_caller]
+    fn div_assign(&mut self, rhs: f32) {
+        for axis in &mut self.axes {
+            *axis /= rhs;
+        }
+    }
+}
+
+impl<T: StatValue, const N: usize> DivAssign<[f32; N]> for VectorStat<T, N> {
+    /// Divides the multiplier of each axis, component-wise.
+    ///
+    /// In debug builds, this panics if a resulting multiplier is NaN or infinite.
+    #[track_caller]
+    fn div_assign(&mut self, rhs: [f32; N]) {
+        for (axis, rhs) in self.axes.iter_mut().zip(rhs) {
+            *axis /= rhs;
+        }
+    }
+}
+
+impl<T: StatValue, const N: usize> Display for VectorStat<T, N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[")?;
+
+        for (index, axis) in self.axes.iter().enumerate() {
+            if index > 0 {
+                write!(f, ", ")?;
+            }
+
+            axis.fmt(f)?;
+        }
+
+        write!(f, "]")
+    }
+}
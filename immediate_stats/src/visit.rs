@@ -0,0 +1,240 @@
+//! Contains visitors for walking every stat in a [`StatContainer`], along with its path.
+
+use crate::StatContainer;
+use crate::parse::ParseStatError;
+use std::any::Any;
+use std::fmt::{Debug, Display, Write};
+
+/// A stat with its value type erased, so that stats of different types can be visited together.
+///
+/// This is implemented for [`Stat`](crate::Stat), [`LayeredStat`](crate::LayeredStat),
+/// [`TrackedStat`](crate::TrackedStat) and `FixedStat`. Other stats, such as [`DerivedStat`](crate::DerivedStat),
+/// are visited using the stat that modifiers are applied to.
+/// Use [`downcast_ref`](AnyStat#method.downcast_ref) to access the concrete type.
+pub trait AnyStat: Any + Debug + Display {
+    /// Multiplies the stat's multiplier, which is the same as using `*=`.
+    fn multiply(&mut self, multiplier: f32);
+
+    /// Parses a modifier for this type of stat, using the same text format as
+    /// [`Modifier::from_str`](crate::Modifier#method.from_str), such as `+5 x1.5`.
+    fn parse_modifier(&self, text: &str) -> Result<AnyModifier, ParseStatError>;
+
+    /// Applies a modifier returned by [`parse_modifier`](AnyStat::parse_modifier).
+    ///
+    /// Returns false if the modifier was parsed by a different type of stat.
+    fn apply_modifier(&mut self, modifier: &AnyModifier) -> bool;
+}
+
+impl dyn AnyStat {
+    /// Returns true if the stat is of type `S`.
+    pub fn is<S: AnyStat>(&self) -> bool {
+        (self as &dyn Any).is::<S>()
+    }
+
+    /// Returns the stat as type `S`, or `None` if it is a different type.
+    pub fn downcast_ref<S: AnyStat>(&self) -> Option<&S> {
+        (self as &dyn Any).downcast_ref()
+    }
+
+    /// Returns the stat as type `S`, or `None` if it is a different type.
+    pub fn downcast_mut<S: AnyStat>(&mut self) -> Option<&mut S> {
+        (self as &mut dyn Any).downcast_mut()
+    }
+}
+
+/// A modifier with its type erased, which is returned by [`AnyStat::parse_modifier`].
+/// ```rust
+/// # use immediate_stats::*;
+/// let mut speed = Stat::new(10);
+/// let mut jump = Stat::new(2.0);
+///
+/// let modifier = (&speed as &dyn AnyStat).parse_modifier("x1.5").unwrap();
+/// assert_eq!(modifier.downcast_ref(), Some(&Modifier::<i32>::from_multiplier(1.5)));
+///
+/// assert!(speed.apply_modifier(&modifier));
+/// assert_eq!(speed.total(), 15);
+///
+/// // The modifier was parsed for an `i32` stat, so it can't be applied to an `f64` stat.
+/// assert!(!jump.apply_modifier(&modifier));
+/// ```
+pub struct AnyModifier(Box<dyn Any + Send + Sync>);
+
+impl AnyModifier {
+    /// Erases the type of a modifier, such as a [`Modifier`](crate::Modifier).
+    pub fn new<M: Any + Send + Sync>(modifier: M) -> Self {
+        Self(Box::new(modifier))
+    }
+
+    /// Returns the modifier as type `M`, or `None` if it is a different type.
+    pub fn downcast_ref<M: Any>(&self) -> Option<&M> {
+        self.0.downcast_ref()
+    }
+}
+
+impl Debug for AnyModifier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AnyModifier").finish_non_exhaustive()
+    }
+}
+
+/// The dotted path of the stat currently being visited, such as `movement.speed`.
+#[derive(Default)]
+struct Path(String);
+
+impl Path {
+    /// Appends a field name, returning the previous length so that it can be removed.
+    fn push_field(&mut self, name: &str) -> usize {
+        let len = self.0.len();
+
+        if len > 0 {
+            self.0.push('.');
+        }
+
+        self.0.push_str(name);
+        len
+    }
+
+    /// Appends a map key, such as `["speed"]`, returning the previous length.
+    fn push_key(&mut self, key: &dyn Debug) -> usize {
+        let len = self.0.len();
+        // Writing to a `String` can't fail.
+        let _ = write!(self.0, "[{key:?}]");
+        len
+    }
+}
+
+/// Visits every stat in a [`StatContainer`], using [`StatContainer::visit_stats`].
+///
+/// This is usually created by [`StatContainer::for_each_stat`].
+/// ```rust
+/// # use immediate_stats::*;
+/// #[derive(StatContainer)]
+/// struct Movement {
+///     speed: Stat,
+///     jump: Stat<f32>,
+/// }
+///
+/// #[derive(StatContainer)]
+/// struct Player {
+///     #[stat]
+///     movement: Movement,
+///     #[stat]
+///     health: Pool,
+/// }
+///
+/// let player = Player {
+///     movement: Movement {
+///         speed: Stat::new(10),
+///         jump: Stat::new(2.5),
+///     },
+///     health: Pool::new(100),
+/// };
+///
+/// let mut paths = Vec::new();
+/// player.for_each_stat(|path, stat| paths.push(format!("{path}: {stat}")));
+///
+/// assert_eq!(
+///     paths,
+///     [
+///         "movement.speed: (10 + 0) x 1",
+///         "movement.jump: (2.5 + 0) x 1",
+///         "health: (100 + 0) x 1",
+///     ]
+/// );
+/// ```
+pub struct StatVisitor<'a> {
+    path: Path,
+    callback: &'a mut dyn FnMut(&str, &dyn AnyStat),
+}
+
+impl<'a> StatVisitor<'a> {
+    /// Creates a visitor that calls `callback` with the path of each stat.
+    pub fn new(callback: &'a mut dyn FnMut(&str, &dyn AnyStat)) -> Self {
+        Self {
+            path: Path::default(),
+            callback,
+        }
+    }
+
+    /// Returns the path of the container currently being visited.
+    pub fn path(&self) -> &str {
+        &self.path.0
+    }
+
+    /// Calls the callback with a stat, using the current path.
+    pub fn visit(&mut self, stat: &dyn AnyStat) {
+        (self.callback)(&self.path.0, stat);
+    }
+
+    /// Visits a sub-container, adding its name to the path, such as `speed` or `0`.
+    pub fn field(&mut self, name: &str, container: &(impl StatContainer + ?Sized)) {
+        let len = self.path.push_field(name);
+        container.visit_stats(self);
+        self.path.0.truncate(len);
+    }
+
+    /// Visits a sub-container that is stored under a key, such as an entry of a [`StatMap`](crate::StatMap).
+    pub fn entry(&mut self, key: &dyn Debug, container: &(impl StatContainer + ?Sized)) {
+        let len = self.path.push_key(key);
+        container.visit_stats(self);
+        self.path.0.truncate(len);
+    }
+}
+
+/// Visits every stat in a [`StatContainer`] mutably, using [`StatContainer::visit_stats_mut`].
+///
+/// This is usually created by [`StatContainer::for_each_stat_mut`].
+/// ```rust
+/// # use immediate_stats::*;
+/// #[derive(StatContainer)]
+/// enum Enemy {
+///     Walker { speed: Stat },
+///     Flyer { speed: Stat<f32>, altitude: Stat<f32> },
+/// }
+///
+/// let mut enemy = Enemy::Walker { speed: Stat::new(10) };
+///
+/// // Slow everything by 50%.
+/// enemy.for_each_stat_mut(|_, stat| stat.multiply(0.5));
+///
+/// let Enemy::Walker { speed } = enemy else { unreachable!() };
+/// assert_eq!(speed.total(), 5);
+/// ```
+pub struct StatVisitorMut<'a> {
+    path: Path,
+    callback: &'a mut dyn FnMut(&str, &mut dyn AnyStat),
+}
+
+impl<'a> StatVisitorMut<'a> {
+    /// Creates a visitor that calls `callback` with the path of each stat.
+    pub fn new(callback: &'a mut dyn FnMut(&str, &mut dyn AnyStat)) -> Self {
+        Self {
+            path: Path::default(),
+            callback,
+        }
+    }
+
+    /// Returns the path of the container currently being visited.
+    pub fn path(&self) -> &str {
+        &self.path.0
+    }
+
+    /// Calls the callback with a stat, using the current path.
+    pub fn visit(&mut self, stat: &mut dyn AnyStat) {
+        (self.callback)(&self.path.0, stat);
+    }
+
+    /// Visits a sub-container, adding its name to the path, such as `speed` or `0`.
+    pub fn field(&mut self, name: &str, container: &mut (impl StatContainer + ?Sized)) {
+        let len = self.path.push_field(name);
+        container.visit_stats_mut(self);
+        self.path.0.truncate(len);
+    }
+
+    /// Visits a sub-container that is stored under a key, such as an entry of a [`StatMap`](crate::StatMap).
+    pub fn entry(&mut self, key: &dyn Debug, container: &mut (impl StatContainer + ?Sized)) {
+        let len = self.path.push_key(key);
+        container.visit_stats_mut(self);
+        self.path.0.truncate(len);
+    }
+}
@@ -2,6 +2,7 @@
 #![cfg(feature = "bevy")]
 
 use bevy_ecs::prelude::*;
+use bevy_reflect::{PartialReflect, Reflect, ReflectRef};
 use immediate_stats::*;
 
 #[derive(Component, Resource, StatContainer, PartialEq, Debug, Clone)]
@@ -17,6 +18,7 @@ fn reset_component() {
             base: 100,
             bonus: 50,
             multiplier: 2.0,
+            ..Default::default()
         }))
         .id();
 
@@ -37,6 +39,7 @@ fn pause_component() {
         base: 100,
         bonus: 50,
         multiplier: 2.0,
+        ..Default::default()
     });
 
     let entity = world.spawn((health.clone(), PauseStatReset)).id();
@@ -55,6 +58,7 @@ fn reset_resource() {
         base: 100,
         bonus: 50,
         multiplier: 2.0,
+        ..Default::default()
     }));
 
     world.run_system(system).unwrap();
@@ -71,3 +75,107 @@ fn reset_resource_not_present() {
     let system = world.register_system(reset_resource_modifiers::<Health>);
     world.run_system(system).unwrap();
 }
+
+#[test]
+fn register_generic_types() {
+    let mut app = bevy_app::App::new();
+    app.add_plugins(ImmediateStatsPlugin);
+
+    let registry = app.world().resource::<AppTypeRegistry>().read();
+    assert!(registry.contains(std::any::TypeId::of::<Stat>()));
+    assert!(registry.contains(std::any::TypeId::of::<Stat<f32>>()));
+    assert!(registry.contains(std::any::TypeId::of::<Modifier<u64>>()));
+    assert!(registry.contains(std::any::TypeId::of::<Vec3Stat>()));
+}
+
+#[derive(Component, StatContainer, PartialEq, Debug, Default)]
+struct Attack {
+    strength: Stat,
+    #[derived(self.strength.total() * 2)]
+    power: DerivedStat,
+}
+
+#[test]
+fn update_derived_component() {
+    let mut world = World::new();
+    let system = world.register_system(update_component_derived::<Attack>);
+
+    let mut attack = Attack {
+        strength: Stat::new(10),
+        ..Default::default()
+    };
+    attack.strength += 5;
+
+    let entity = world.spawn((attack, PauseStatReset)).id();
+
+    world.run_system(system).unwrap();
+
+    assert_eq!(world.get::<Attack>(entity).unwrap().power.total(), 30);
+}
+
+#[derive(Component, StatContainer)]
+struct Speed(SnapshotStat);
+
+#[test]
+fn reset_component_snapshot() {
+    let mut world = World::new();
+    let system = world.register_system(reset_component_modifiers::<Speed>);
+
+    let mut speed = Speed(SnapshotStat::new(10));
+    speed.0 *= 0.5;
+    let entity = world.spawn(speed).id();
+
+    world.run_system(system).unwrap();
+
+    let speed = world.get::<Speed>(entity).unwrap();
+    assert_eq!(speed.0.previous_total(), 5);
+    assert!(speed.0.changed());
+}
+
+#[derive(Reflect, PartialEq, Eq, PartialOrd, Ord, Debug, Clone)]
+enum Attribute {
+    Strength,
+    Luck,
+}
+
+#[test]
+fn reset_component_stat_map() {
+    let mut world = World::new();
+    let system = world.register_system(reset_component_modifiers::<StatMap<Attribute>>);
+
+    let mut stats = StatMap::new(10);
+    stats[&Attribute::Strength] += 5;
+    let entity = world.spawn(stats).id();
+
+    world.run_system(system).unwrap();
+
+    let stats = world.get::<StatMap<Attribute>>(entity).unwrap();
+    assert_eq!(stats.total(&Attribute::Strength), 10);
+}
+
+#[test]
+fn reflect_stat_map() {
+    let mut app = bevy_app::App::new();
+    app.add_plugins(ImmediateStatsPlugin)
+        .register_type::<StatMap<Attribute>>();
+
+    let registry = app.world().resource::<AppTypeRegistry>().read();
+    assert!(registry.contains(std::any::TypeId::of::<StatMap<String>>()));
+
+    let registration = registry
+        .get(std::any::TypeId::of::<StatMap<Attribute>>())
+        .unwrap();
+    assert!(registration.data::<ReflectComponent>().is_some());
+
+    let stats = StatMap::new(10).with_base(Attribute::Luck, 3);
+    let ReflectRef::Struct(reflected) = stats.reflect_ref() else {
+        panic!("`StatMap` should reflect as a struct");
+    };
+    assert_eq!(
+        reflected
+            .field("default")
+            .unwrap()
+            .try_downcast_ref::<Stat>(),
+        Some(&Stat::new(10))
+    );
+}
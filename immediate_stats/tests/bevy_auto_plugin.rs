@@ -34,6 +34,7 @@ fn reset_component_auto() {
             base: 100,
             bonus: 50,
             multiplier: 2.0,
+            ..Default::default()
         }))
         .id();
 
@@ -55,6 +56,7 @@ fn reset_resource_auto() {
         base: 100,
         bonus: 50,
         multiplier: 2.0,
+        ..Default::default()
     }));
 
     app.update();
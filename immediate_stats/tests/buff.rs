@@ -0,0 +1,193 @@
+//! Tests loading and applying buff definitions.
+#![cfg(feature = "buffs")]
+
+use immediate_stats::*;
+
+#[derive(StatContainer, Default)]
+struct Character {
+    speed: Stat,
+    strength: Stat,
+    jump: Stat<f32>,
+    #[stat]
+    health: Pool,
+}
+
+impl Character {
+    fn new() -> Self {
+        Self {
+            speed: Stat::new(10),
+            strength: Stat::new(10),
+            jump: Stat::new(2.0),
+            health: Pool::new(100),
+        }
+    }
+}
+
+fn schema() -> BuffSchema<Character> {
+    BuffSchema::new(Character::new())
+}
+
+#[test]
+fn load_str() {
+    let buffs = schema()
+        .load_str(
+            "
+            haste: { speed: x1.3 }
+            weakened: { strength: -5 }
+            ",
+        )
+        .unwrap();
+
+    assert_eq!(buffs.len(), 2);
+
+    let mut character = Character::new();
+    buffs["haste"].apply(&mut character);
+    assert_eq!(character.speed.total(), 13);
+
+    assert!(buffs.apply("weakened", &mut character));
+    assert_eq!(character.strength.total(), 5);
+}
+
+#[test]
+fn load_str_separators() {
+    let buffs = schema()
+        .load_str(r#"{ haste: { speed: (+5) x 2, "jump": "+0.5" }, "weakened": { strength: -5 } }"#)
+        .unwrap();
+
+    let mut character = Character::new();
+    buffs["haste"].apply(&mut character);
+    assert_eq!(character.speed.total(), 30);
+    assert_eq!(character.jump.total(), 2.5);
+
+    assert!(schema().load_str("haste: { speed x1.3 }").is_err());
+    assert!(schema().load_str("haste: { speed: x1.3").is_err());
+    assert!(schema().load_str("{ haste: { speed: x1.3 }").is_err());
+}
+
+#[test]
+fn nested_paths() {
+    #[derive(StatContainer, Default)]
+    struct Aim(Stat<f32>, Stat<f32>);
+
+    #[derive(StatContainer, Default)]
+    struct Player {
+        #[stat]
+        character: Character,
+        #[stat]
+        aim: Aim,
+    }
+
+    let schema = BuffSchema::<Player>::default();
+    assert!(schema.contains("character.speed"));
+    assert!(schema.contains("aim.1"));
+    assert!(!schema.contains("speed"));
+
+    let buffs = schema
+        .load_str("steady: { character.speed: -2, aim.1: x0.5 }")
+        .unwrap();
+
+    let mut player = Player {
+        character: Character::new(),
+        aim: Aim(Stat::new(1.0), Stat::new(4.0)),
+    };
+    buffs["steady"].apply(&mut player);
+    assert_eq!(player.character.speed.total(), 8);
+    assert_eq!(player.aim.1.total(), 2.0);
+}
+
+#[test]
+fn load_ron() {
+    let buffs = schema()
+        .load_ron(
+            r#"{
+                "haste": { "speed": "x1.3", "jump": "+0.5" },
+                "weakened": { "strength": "-5" },
+                "vitality": { "health": "+20%" },
+            }"#,
+        )
+        .unwrap();
+
+    assert_eq!(buffs.len(), 3);
+
+    let mut character = Character::new();
+    buffs["haste"].apply(&mut character);
+    assert_eq!(character.speed.total(), 13);
+    assert_eq!(character.jump.total(), 2.5);
+
+    assert!(buffs.apply("weakened", &mut character));
+    assert_eq!(character.strength.total(), 5);
+
+    assert!(buffs.apply("vitality", &mut character));
+    assert_eq!(character.health.max.total(), 120);
+
+    assert!(!buffs.apply("missing", &mut character));
+}
+
+#[test]
+fn load_json() {
+    let buffs = schema()
+        .load_json(r#"{ "haste": { "speed": "(+5) x 2" } }"#)
+        .unwrap();
+
+    let mut character = Character::new();
+    buffs["haste"].apply(&mut character);
+    assert_eq!(character.speed.total(), 30);
+
+    character.reset_modifiers();
+    assert_eq!(character.speed.total(), 10);
+}
+
+#[test]
+fn stat_names() {
+    let buffs = schema()
+        .load_json(r#"{ "haste": { "speed": "x2", "jump": "x2" } }"#)
+        .unwrap();
+
+    let mut stats = buffs["haste"].stats().collect::<Vec<_>>();
+    stats.sort();
+    assert_eq!(stats, ["jump", "speed"]);
+}
+
+#[test]
+fn unknown_stat() {
+    let error = schema()
+        .load_ron(r#"{ "haste": { "sped": "x1.3" } }"#)
+        .unwrap_err();
+
+    assert_eq!(
+        error,
+        BuffError::UnknownStat {
+            buff: "haste".into(),
+            stat: "sped".into(),
+        }
+    );
+    assert_eq!(
+        error.to_string(),
+        "buff `haste` modifies unknown stat `sped`"
+    );
+}
+
+#[test]
+fn invalid_modifier() {
+    // `speed` is an integer stat, so the bonus can't have a fraction.
+    let error = schema()
+        .load_json(r#"{ "haste": { "speed": "+1.5" } }"#)
+        .unwrap_err();
+
+    assert!(matches!(
+        error,
+        BuffError::InvalidModifier { ref buff, ref stat, .. } if buff == "haste" && stat == "speed"
+    ));
+    assert_eq!(
+        error.to_string(),
+        "buff `haste` has an invalid modifier for `speed`: `1.5` is not a valid number at position 1"
+    );
+}
+
+#[test]
+fn invalid_format() {
+    let error = schema()
+        .load_json(r#"{ "haste": { "speed": 1.3 } }"#)
+        .unwrap_err();
+    assert!(matches!(error, BuffError::Format(_)));
+}
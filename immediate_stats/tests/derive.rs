@@ -24,6 +24,7 @@ fn reset() {
                 base,
                 bonus: 3,
                 multiplier: 1.5,
+                ..Default::default()
             },
             custom: MyStat,
             other: true,
@@ -53,6 +54,7 @@ fn reset_tuple_struct() {
                 base,
                 bonus: 3,
                 multiplier: 1.5,
+                ..Default::default()
             },
             true,
         );
@@ -79,6 +81,7 @@ fn reset_with_attribute() {
                     base,
                     bonus: 3,
                     multiplier: 1.5,
+                    ..Default::default()
                 },
                 true,
             ),
@@ -105,6 +108,7 @@ fn reset_ignored() {
             base,
             bonus: 3,
             multiplier: 1.5,
+            ..Default::default()
         };
 
         let mut partial = PartialReset {
@@ -138,6 +142,7 @@ fn reset_enum_named() {
                 base,
                 bonus: 3,
                 multiplier: 1.5,
+                ..Default::default()
             },
             other: 0,
         };
@@ -162,6 +167,7 @@ fn reset_enum_unnamed() {
                 base,
                 bonus: 3,
                 multiplier: 1.5,
+                ..Default::default()
             },
             0,
         );
@@ -171,3 +177,137 @@ fn reset_enum_unnamed() {
         assert_eq!(stat, EnumStat::Unnamed(Stat::new(base), 0));
     }
 }
+
+#[derive(StatContainer, PartialEq, Debug)]
+struct Attack {
+    speed: Stat<f32>,
+    damage: Stat<u64>,
+}
+
+#[test]
+fn reset_generic_stats() {
+    let mut attack = Attack {
+        speed: Stat::new(1.25).with_bonus(0.5),
+        damage: Stat::new(10).with_multiplier(2.0),
+    };
+
+    attack.reset_modifiers();
+
+    assert_eq!(
+        attack,
+        Attack {
+            speed: Stat::new(1.25),
+            damage: Stat::new(10),
+        }
+    );
+}
+
+#[derive(StatContainer, PartialEq, Debug)]
+struct Generic<T: StatValue>(Stat<T>);
+
+#[test]
+fn reset_generic_container() {
+    let mut generic = Generic(Stat::new(4.5).with_bonus(1.0));
+    generic.reset_modifiers();
+    assert_eq!(generic, Generic(Stat::new(4.5)));
+}
+
+#[derive(StatContainer, PartialEq, Debug)]
+struct Damage(LayeredStat);
+
+#[test]
+fn reset_layered() {
+    let mut damage = Damage(LayeredStat::new(10).with_increased(0.5).with_final_flat(3));
+    damage.reset_modifiers();
+    assert_eq!(damage, Damage(LayeredStat::new(10)));
+}
+
+#[derive(StatContainer, PartialEq, Debug, Default)]
+struct Combat {
+    // Declared before its dependency, to test ordering.
+    #[derived(self.attack.total() / 10)]
+    crit_chance: DerivedStat,
+    #[derived(self.strength.total() * 2 + self.weapon.total())]
+    attack: DerivedStat,
+    strength: Stat,
+    weapon: Stat,
+}
+
+#[test]
+fn update_derived() {
+    let mut combat = Combat {
+        strength: Stat::new(10),
+        weapon: Stat::new(5),
+        ..Default::default()
+    };
+
+    combat.strength += 5;
+    combat.update_derived();
+
+    assert_eq!(combat.attack.total(), 35);
+    assert_eq!(combat.crit_chance.total(), 3);
+}
+
+#[test]
+fn update_derived_with_modifiers() {
+    let mut combat = Combat {
+        strength: Stat::new(10),
+        weapon: Stat::new(5),
+        ..Default::default()
+    };
+
+    combat.attack *= 2.0;
+    combat.update_derived();
+    assert_eq!(combat.attack.base(), 25);
+    assert_eq!(combat.attack.total(), 50);
+    assert_eq!(combat.crit_chance.total(), 5);
+
+    combat.reset_modifiers();
+    assert_eq!(combat.attack.total(), 25);
+}
+
+#[derive(StatContainer, PartialEq, Debug, Default)]
+struct Character {
+    #[stat]
+    combat: Combat,
+    #[derived(self.combat.crit_chance.total() * 2)]
+    luck: DerivedStat,
+}
+
+#[derive(StatContainer, PartialEq, Debug, Default)]
+struct Scaled(Stat, #[derived(self.0.total() * 3)] DerivedStat);
+
+#[test]
+fn update_derived_nested() {
+    let mut character = Character {
+        combat: Combat {
+            strength: Stat::new(50),
+            ..Default::default()
+        },
+        luck: DerivedStat::default(),
+    };
+
+    character.update_derived();
+    assert_eq!(character.combat.crit_chance.total(), 10);
+    assert_eq!(character.luck.total(), 20);
+}
+
+#[test]
+fn update_derived_tuple_struct() {
+    let mut scaled = Scaled(Stat::new(4), DerivedStat::default());
+    scaled.0 += 1;
+    scaled.update_derived();
+    assert_eq!(scaled.1.total(), 15);
+}
+
+#[derive(StatContainer, PartialEq, Debug)]
+enum Offset {
+    After(u8, Stat),
+}
+
+#[test]
+fn reset_enum_unnamed_offset() {
+    let mut offset = Offset::After(1, Stat::new(10).with_bonus(5));
+    offset.reset_modifiers();
+    assert_eq!(offset, Offset::After(1, Stat::new(10)));
+}
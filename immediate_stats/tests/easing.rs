@@ -0,0 +1,68 @@
+//! Tests the built-in easing curves and scaling modifiers with them.
+
+use immediate_stats::*;
+
+const EASINGS: [Easing; 5] = [
+    Easing::Linear,
+    Easing::EaseIn,
+    Easing::EaseOut,
+    Easing::EaseInOut,
+    Easing::SmoothStep,
+];
+
+#[test]
+fn endpoints() {
+    for easing in EASINGS {
+        assert_eq!(easing.ease(0.0), 0.0, "{easing:?}");
+        assert_eq!(easing.ease(1.0), 1.0, "{easing:?}");
+    }
+}
+
+#[test]
+fn midpoints() {
+    assert_eq!(Easing::Linear.ease(0.5), 0.5);
+    assert_eq!(Easing::EaseIn.ease(0.5), 0.25);
+    assert_eq!(Easing::EaseOut.ease(0.5), 0.75);
+    assert_eq!(Easing::EaseInOut.ease(0.5), 0.5);
+    assert_eq!(Easing::EaseInOut.ease(0.25), 0.125);
+    assert_eq!(Easing::SmoothStep.ease(0.5), 0.5);
+    assert_eq!(Easing::SmoothStep.ease(0.25), 0.15625);
+}
+
+#[test]
+fn scaled_with_endpoints() {
+    let modifier = Modifier::<i32>::new(10, 3.0);
+
+    for easing in EASINGS {
+        assert_eq!(modifier.scaled_with(0.0, &easing), modifier.scaled(0.0));
+        assert_eq!(modifier.scaled_with(1.0, &easing), modifier);
+    }
+}
+
+#[test]
+fn scaled_with() {
+    let modifier = Modifier::<i32>::new(10, 3.0);
+    assert_eq!(
+        modifier.scaled_with(0.5, &Easing::EaseIn),
+        Modifier::new(2, 1.5)
+    );
+}
+
+#[test]
+fn scaled_with_closure() {
+    let modifier = Modifier::<i32>::new(0, 0.2);
+    let step = |t: f32| if t < 0.5 { 0.0 } else { 1.0 };
+
+    assert_eq!(modifier.scaled_with(0.4, &step), Modifier::default());
+    assert_eq!(modifier.scaled_with(0.6, &step), modifier);
+}
+
+#[test]
+fn apply_scaled_with() {
+    let mut stat = Stat::new(10);
+    stat.apply_scaled_with(Modifier::new(8, 5.0), 0.5, &Easing::EaseOut);
+
+    assert_eq!(stat.bonus, 6);
+    assert_eq!(stat.multiplier, 4.0);
+    assert_eq!(stat.total(), 64);
+}
@@ -0,0 +1,135 @@
+//! Tests the deterministic fixed-point stats.
+#![cfg(feature = "fixed_point")]
+
+use immediate_stats::*;
+
+#[test]
+fn fixed_conversions() {
+    assert_eq!(Fixed::from_int(1), Fixed::ONE);
+    assert_eq!(Fixed::from_ratio(3, 2).to_bits(), 3 << 31);
+    assert_eq!(Fixed::from_f32(1.5), Fixed::from_ratio(3, 2));
+    assert_eq!(Fixed::from_ratio(3, 2).to_f32(), 1.5);
+}
+
+#[test]
+fn fixed_arithmetic() {
+    let half = Fixed::from_ratio(1, 2);
+    assert_eq!(half * Fixed::from_int(3), Fixed::from_ratio(3, 2));
+    assert_eq!(Fixed::ONE / Fixed::from_int(4), Fixed::from_ratio(1, 4));
+    assert_eq!(half + half, Fixed::ONE);
+    assert_eq!(Fixed::ONE - half, half);
+}
+
+#[test]
+fn reset() {
+    for base in 0..10 {
+        let mut stat = FixedStat::new(base)
+            .with_bonus(3)
+            .with_multiplier(Fixed::from_ratio(3, 2));
+        stat.reset_modifiers();
+        assert_eq!(stat, FixedStat::new(base));
+    }
+}
+
+#[test]
+fn total() {
+    let mut stat = FixedStat::new(10);
+    stat += 5;
+    stat *= Fixed::from_int(2);
+    assert_eq!(stat.total(), 30);
+
+    stat /= Fixed::from_int(4);
+    assert_eq!(stat.total(), 7);
+}
+
+#[test]
+fn total_rounding() {
+    let stat = FixedStat::new(15).with_multiplier(Fixed::from_ratio(99, 100));
+    assert_eq!(stat.total(), 14);
+    assert_eq!(stat.with_rounding(Rounding::HalfAway).total(), 15);
+
+    let stat = FixedStat::new(-5).with_multiplier(Fixed::from_ratio(1, 2));
+    assert_eq!(stat.total(), -2);
+    assert_eq!(stat.with_rounding(Rounding::Floor).total(), -3);
+    assert_eq!(stat.with_rounding(Rounding::Ceil).total(), -2);
+    assert_eq!(stat.with_rounding(Rounding::HalfEven).total(), -2);
+    assert_eq!(stat.with_rounding(Rounding::HalfAway).total(), -3);
+}
+
+#[test]
+fn debuff_unsigned() {
+    let mut stat: FixedStat<u32> = FixedStat::new(10);
+    stat -= 5;
+    assert_eq!(stat.bonus, -5);
+    assert_eq!(stat.total(), 5);
+
+    // Debuffs larger than the base saturate at zero.
+    stat.apply(FixedModifier::from_bonus(-10));
+    assert_eq!(stat.total(), 0);
+}
+
+#[test]
+fn total_saturates() {
+    let stat: FixedStat<u8> = FixedStat::new(200).with_multiplier(Fixed::from_int(2));
+    assert_eq!(stat.total(), u8::MAX);
+}
+
+#[test]
+fn scaled() {
+    let modifier: FixedModifier = FixedModifier::new(10, Fixed::from_int(3));
+
+    assert_eq!(modifier.scaled(Fixed::ZERO), FixedModifier::default());
+    assert_eq!(modifier.scaled(Fixed::ONE), modifier);
+    assert_eq!(
+        modifier.scaled(Fixed::from_ratio(1, 2)),
+        FixedModifier::new(5, Fixed::from_int(2))
+    );
+}
+
+/// Applies a long sequence of modifiers that would accumulate rounding error with floats.
+fn simulate() -> FixedStat {
+    let mut stat = FixedStat::new(1_000);
+
+    for tick in 0..1_000i64 {
+        let modifier = FixedModifier::new(
+            tick as i32 % 7 - 3,
+            Fixed::from_ratio(1_000 + tick % 13, 1_003),
+        );
+        stat.apply_scaled(modifier, Fixed::from_ratio(tick % 5, 4));
+    }
+
+    stat
+}
+
+#[test]
+fn deterministic() {
+    let first = simulate();
+    let second = simulate();
+
+    assert_eq!(first.multiplier.to_bits(), second.multiplier.to_bits());
+    assert_eq!(first.total(), second.total());
+}
+
+#[test]
+fn deterministic_golden_value() {
+    // If this changes, totals are no longer bit-identical with previous versions.
+    let stat = simulate();
+    assert_eq!(stat.bonus, 0);
+    assert_eq!(stat.multiplier.to_bits(), 19_076_232_328);
+    assert_eq!(stat.total(), 4_441);
+}
+
+#[test]
+fn parse_any_modifier() {
+    let mut stat = FixedStat::new(10);
+
+    let modifier = (&stat as &dyn AnyStat).parse_modifier("+5 x2").unwrap();
+    assert!(stat.apply_modifier(&modifier));
+    assert_eq!(stat.total(), 30);
+
+    let error = (&stat as &dyn AnyStat).parse_modifier("+20%").unwrap_err();
+    assert_eq!(
+        error.kind(),
+        &ParseStatErrorKind::Unsupported("a percentage")
+    );
+}
@@ -0,0 +1,195 @@
+//! Tests parsing and evaluating formulas.
+
+use immediate_stats::*;
+use std::collections::HashMap;
+
+fn evaluate(formula: &str, variables: &impl Variables) -> Result<f64, FormulaError> {
+    formula.parse::<Formula>()?.evaluate(variables)
+}
+
+#[test]
+fn precedence() {
+    assert_eq!(evaluate("2 * 3 + 4", &[]), Ok(10.0));
+    assert_eq!(evaluate("2 * (3 + 4)", &[]), Ok(14.0));
+    assert_eq!(evaluate("10 - 4 - 3", &[]), Ok(3.0));
+    assert_eq!(evaluate("12 / 3 / 2", &[]), Ok(2.0));
+    assert_eq!(evaluate("-2 * -(1 + 2)", &[]), Ok(6.0));
+}
+
+#[test]
+fn variables() {
+    let variables = HashMap::from([("level", 5.0), ("movement.speed", 2.5)]);
+    assert_eq!(evaluate("2 * level + 3", &variables), Ok(13.0));
+    assert_eq!(evaluate("movement.speed * 2", &variables), Ok(5.0));
+
+    let closure = |name: &str| (name == "agility").then_some(50.0);
+    assert_eq!(evaluate("1 + 0.01 * agility", &closure), Ok(1.5));
+}
+
+#[test]
+fn undefined_variable() {
+    assert_eq!(
+        evaluate("2 * level", &[("agility", 1.0)]),
+        Err(FormulaError::UndefinedVariable("level".into()))
+    );
+}
+
+#[test]
+fn division_by_zero() {
+    let formula: Formula = "10 / (level - 1)".parse().unwrap();
+    assert_eq!(formula.evaluate(&[("level", 2.0)]), Ok(10.0));
+    assert_eq!(
+        formula.evaluate(&[("level", 1.0)]),
+        Err(FormulaError::DivisionByZero)
+    );
+}
+
+#[test]
+fn syntax_errors() {
+    assert_eq!(
+        "2 * (3 + 4".parse::<Formula>(),
+        Err(FormulaError::Expected {
+            position: 10,
+            expected: "`)`",
+            found: None,
+        })
+    );
+    assert_eq!(
+        "2 level".parse::<Formula>(),
+        Err(FormulaError::Expected {
+            position: 2,
+            expected: "an operator",
+            found: Some('l'),
+        })
+    );
+    assert_eq!(
+        "1.2.3".parse::<Formula>(),
+        Err(FormulaError::InvalidNumber {
+            position: 0,
+            number: "1.2.3".into(),
+        })
+    );
+    assert_eq!(
+        "2 + * 3".parse::<Formula>().unwrap_err().to_string(),
+        "expected a number, variable or `(`, but found `*` at position 4"
+    );
+}
+
+#[test]
+fn too_deep() {
+    let nested = format!("{}1{}", "(".repeat(1000), ")".repeat(1000));
+    assert!(matches!(
+        nested.parse::<Formula>(),
+        Err(FormulaError::TooDeep { .. })
+    ));
+
+    let long = vec!["1"; 1000].join(" + ");
+    assert!(matches!(
+        long.parse::<Formula>(),
+        Err(FormulaError::TooDeep { .. })
+    ));
+
+    let reasonable = vec!["1"; 100].join(" + ");
+    assert_eq!(evaluate(&reasonable, &[]), Ok(100.0));
+}
+
+#[test]
+fn modifier_formula() {
+    let formula: ModifierFormula = "bonus = 2 * level + 3; multiplier = 1 + 0.01 * agility"
+        .parse()
+        .unwrap();
+
+    let variables = [("level", 5.0), ("agility", 50.0)];
+    assert_eq!(
+        formula.evaluate(&variables),
+        Ok(Modifier::<i32>::new(13, 1.5))
+    );
+
+    let formula: ModifierFormula = "base_percent = level\n".parse().unwrap();
+    assert_eq!(
+        formula.evaluate(&variables),
+        Ok(Modifier::<i32>::from_base_percent(5.0))
+    );
+}
+
+#[test]
+fn modifier_formula_errors() {
+    assert_eq!(
+        "bonus = 1; bonsu = 2".parse::<ModifierFormula>(),
+        Err(FormulaError::InvalidTarget {
+            position: 11,
+            name: "bonsu".into(),
+        })
+    );
+    assert_eq!(
+        "bonus = 1; bonus = 2".parse::<ModifierFormula>(),
+        Err(FormulaError::InvalidTarget {
+            position: 11,
+            name: "bonus".into(),
+        })
+    );
+    assert_eq!(
+        "bonus 1".parse::<ModifierFormula>(),
+        Err(FormulaError::Expected {
+            position: 6,
+            expected: "`=`",
+            found: Some('1'),
+        })
+    );
+    assert_eq!(
+        "multiplier = 1; bonus = 2 +".parse::<ModifierFormula>(),
+        Err(FormulaError::Expected {
+            position: 27,
+            expected: "a number, variable or `(`",
+            found: None,
+        })
+    );
+}
+
+#[test]
+fn out_of_range() {
+    // Bonuses of `u8` stats are `i16`, so they can be negative.
+    let formula: ModifierFormula = "bonus = -5".parse().unwrap();
+    assert_eq!(formula.evaluate::<u8>(&[]), Ok(Modifier::from_bonus(-5)));
+
+    let formula: ModifierFormula = "bonus = 100000".parse().unwrap();
+    assert_eq!(
+        formula.evaluate::<u8>(&[]),
+        Err(FormulaError::OutOfRange(100000.0))
+    );
+}
+
+#[test]
+fn wide_integer_bonus() {
+    // Larger than the largest integer that an `f32` can represent exactly.
+    let formula: ModifierFormula = "bonus = 16777217".parse().unwrap();
+    assert_eq!(
+        formula.evaluate::<i64>(&[]),
+        Ok(Modifier::from_bonus(16777217))
+    );
+    assert_eq!(
+        formula.evaluate::<u64>(&[]),
+        Ok(Modifier::from_bonus(16777217))
+    );
+
+    let mut stat: Stat<i64> = Stat::new(0);
+    stat.apply_formula(&formula, &[]).unwrap();
+    assert_eq!(stat.total(), 16777217);
+}
+
+#[test]
+fn apply_formula() {
+    let formula: ModifierFormula = "bonus = level / 2".parse().unwrap();
+
+    let mut stat = Stat::new(10);
+    stat.rounding = Rounding::Ceil;
+    stat.apply_formula(&formula, &[("level", 5.0)]).unwrap();
+    assert_eq!(stat.total(), 13);
+
+    let before = stat;
+    assert_eq!(
+        stat.apply_formula(&formula, &[]),
+        Err(FormulaError::UndefinedVariable("level".into()))
+    );
+    assert_eq!(stat, before);
+}
@@ -0,0 +1,126 @@
+//! Tests the various methods of `GroupedStat`.
+
+use immediate_stats::*;
+
+#[test]
+fn reset() {
+    let mut stat: GroupedStat = GroupedStat::new(10);
+    stat += 5;
+    stat.apply_grouped("slow", GroupPolicy::Min, Modifier::from_multiplier(0.5));
+    stat.reset_modifiers();
+
+    assert_eq!(stat, GroupedStat::new(10));
+    assert!(stat.groups().is_empty());
+}
+
+#[test]
+fn ungrouped_stacks() {
+    let mut stat: GroupedStat = GroupedStat::new(10);
+    stat += 5;
+    stat *= 2.0;
+    stat.stat.apply(Modifier::new(5, 2.0));
+    assert_eq!(stat.total(), 80);
+}
+
+#[test]
+fn min_group() {
+    let mut stat: GroupedStat = GroupedStat::new(100);
+    stat.apply_grouped("slow", GroupPolicy::Min, Modifier::from_multiplier(0.8));
+    stat.apply_grouped("slow", GroupPolicy::Min, Modifier::from_multiplier(0.5));
+    stat.apply_grouped("slow", GroupPolicy::Min, Modifier::from_multiplier(0.9));
+
+    assert_eq!(stat.total(), 50);
+}
+
+#[test]
+fn max_group() {
+    let mut stat: GroupedStat = GroupedStat::new(10);
+    stat.apply_grouped("aura", GroupPolicy::Max, Modifier::new(2, 1.5));
+    stat.apply_grouped("aura", GroupPolicy::Max, Modifier::new(6, 1.2));
+
+    assert_eq!(
+        stat.group(&"aura").map(|group| group.modifier),
+        Some(Modifier::new(6, 1.2))
+    );
+    // (10 + 6) x 1.2 = 19.2
+    assert_eq!(stat.total(), 19);
+}
+
+#[test]
+fn max_group_keeps_whole_modifier() {
+    let mut stat: GroupedStat = GroupedStat::new(10);
+    stat.apply_grouped("aura", GroupPolicy::Max, Modifier::new(10, 1.0));
+    stat.apply_grouped("aura", GroupPolicy::Max, Modifier::new(0, 2.0));
+
+    // Mixing the highest bonus with the highest multiplier would give 40.
+    assert_eq!(stat.total(), 20);
+}
+
+#[test]
+fn min_group_keeps_whole_modifier() {
+    let mut stat: GroupedStat = GroupedStat::new(10);
+    stat.apply_grouped("slow", GroupPolicy::Min, Modifier::new(-5, 1.0));
+    stat.apply_grouped("slow", GroupPolicy::Min, Modifier::new(0, 0.8));
+
+    assert_eq!(
+        stat.group(&"slow").map(|group| group.modifier),
+        Some(Modifier::new(-5, 1.0))
+    );
+    assert_eq!(stat.total(), 5);
+}
+
+#[test]
+fn sum_group() {
+    let mut stat: GroupedStat = GroupedStat::new(100);
+    stat.apply_grouped("haste", GroupPolicy::Sum, Modifier::new(1, 1.25));
+    stat.apply_grouped("haste", GroupPolicy::Sum, Modifier::new(1, 1.25));
+
+    assert_eq!(
+        stat.group(&"haste").map(|group| group.modifier),
+        Some(Modifier::new(2, 1.5))
+    );
+    assert_eq!(stat.total(), 153);
+}
+
+#[test]
+fn product_group() {
+    let mut stat: GroupedStat = GroupedStat::new(100);
+    stat.apply_grouped("rage", GroupPolicy::Product, Modifier::from_multiplier(1.5));
+    stat.apply_grouped("rage", GroupPolicy::Product, Modifier::from_multiplier(1.5));
+
+    assert_eq!(stat.total(), 225);
+}
+
+#[test]
+fn separate_groups() {
+    let mut stat: GroupedStat = GroupedStat::new(100);
+    stat.apply_grouped("slow", GroupPolicy::Min, Modifier::from_multiplier(0.5));
+    stat.apply_grouped("haste", GroupPolicy::Max, Modifier::from_multiplier(1.5));
+
+    assert_eq!(stat.groups().len(), 2);
+    assert_eq!(stat.total(), 75);
+}
+
+#[test]
+fn first_policy_is_kept() {
+    let mut stat: GroupedStat = GroupedStat::new(100);
+    stat.apply_grouped("slow", GroupPolicy::Min, Modifier::from_multiplier(0.5));
+    stat.apply_grouped("slow", GroupPolicy::Product, Modifier::from_multiplier(0.5));
+
+    assert_eq!(stat.total(), 50);
+}
+
+#[test]
+fn display() {
+    let mut stat: GroupedStat = GroupedStat::new(10);
+    stat.apply_grouped("slow", GroupPolicy::Min, Modifier::from_multiplier(0.5));
+    assert_eq!(stat.to_string(), "(10 + 0) x 1 {slow: (+0) x 0.5}");
+}
+
+#[test]
+fn max_group_base_percent() {
+    let mut stat = GroupedStat::new(100);
+    stat.apply_grouped("aura", GroupPolicy::Max, Modifier::from_base_percent(10.0));
+    stat.apply_grouped("aura", GroupPolicy::Max, Modifier::from_base_percent(20.0));
+    assert_eq!(stat.total(), 120);
+}
@@ -0,0 +1,104 @@
+//! Tests the various methods of `StatHistory`.
+
+use immediate_stats::*;
+
+#[test]
+fn reset_records_snapshot() {
+    let mut history = StatHistory::new(Stat::new(10), 4);
+    history.container += 5;
+    history.container *= 2.0;
+    history.reset_modifiers();
+
+    assert_eq!(history.container, Stat::new(10));
+    assert_eq!(history.len(), 1);
+
+    let latest = history.latest().unwrap();
+    assert_eq!(latest.total(), 30);
+    assert_eq!(latest.bonus, 5);
+    assert_eq!(latest.multiplier, 2.0);
+}
+
+#[test]
+fn oldest_snapshots_are_removed() {
+    let mut history = StatHistory::new(Stat::new(0), 3);
+
+    for bonus in 0..5 {
+        history.container += bonus;
+        history.reset_modifiers();
+    }
+
+    assert_eq!(history.len(), 3);
+    assert_eq!(history.samples(|stat| stat.total()), vec![2, 3, 4]);
+}
+
+#[test]
+fn zero_capacity() {
+    let mut history = StatHistory::new(Stat::new(10), 0);
+    history.reset_modifiers();
+    assert!(history.is_empty());
+}
+
+#[test]
+fn queries() {
+    let mut history = StatHistory::new(Stat::new(10.0), 10);
+
+    for multiplier in [1.0, 0.5, 2.0, 1.5] {
+        history.container *= multiplier;
+        history.reset_modifiers();
+    }
+
+    assert_eq!(history.min(|stat| stat.total()), Some(5.0));
+    assert_eq!(history.max(|stat| stat.total()), Some(20.0));
+    assert_eq!(history.average(|stat| stat.total()), Some(12.5));
+    assert_eq!(history.max(|stat| stat.multiplier), Some(2.0));
+}
+
+#[test]
+fn empty_queries() {
+    let history = StatHistory::new(Stat::new(10), 10);
+    assert_eq!(history.min(|stat| stat.total()), None);
+    assert_eq!(history.max(|stat| stat.total()), None);
+    assert_eq!(history.average(|stat| stat.total()), None);
+}
+
+#[test]
+fn clear() {
+    let mut history = StatHistory::new(Stat::new(10), 10);
+    history.reset_modifiers();
+    history.clear();
+    assert!(history.is_empty());
+}
+
+#[derive(StatContainer, Clone, PartialEq, Debug)]
+struct Combat {
+    strength: Stat,
+    #[derived(self.strength.total() * 2)]
+    attack: DerivedStat,
+}
+
+#[derive(StatContainer)]
+struct Player {
+    combat: StatHistory<Combat>,
+}
+
+#[test]
+fn nested_container() {
+    let mut player = Player {
+        combat: StatHistory::new(
+            Combat {
+                strength: Stat::new(10),
+                attack: DerivedStat::default(),
+            },
+            8,
+        ),
+    };
+
+    for bonus in [5, 0] {
+        player.combat.container.strength += bonus;
+        player.update_derived();
+        player.reset_modifiers();
+    }
+
+    let attack: Vec<i32> = player.combat.samples(|combat| combat.attack.total());
+    assert_eq!(attack, vec![30, 20]);
+}
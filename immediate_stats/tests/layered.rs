@@ -0,0 +1,162 @@
+//! Tests the various methods of `LayeredStat`.
+
+use immediate_stats::*;
+
+#[test]
+fn reset() {
+    for i in 0..10 {
+        let mut stat = LayeredStat {
+            base: i,
+            flat: 4,
+            increased: 0.5,
+            more: 1.5,
+            final_flat: 2,
+            ..Default::default()
+        };
+        stat.reset_modifiers();
+
+        assert_eq!(stat, LayeredStat::new(i));
+    }
+}
+
+#[test]
+fn default_total() {
+    for i in 0..10 {
+        assert_eq!(LayeredStat::new(i).total(), i);
+    }
+}
+
+#[test]
+fn total() {
+    let stat = LayeredStat::new(10)
+        .with_flat(10)
+        .with_increased(0.5)
+        .with_more(2.0)
+        .with_final_flat(5);
+
+    // ((10 + 10) x 1.5 x 2) + 5 = 65
+    assert_eq!(stat.total(), 65);
+}
+
+#[test]
+fn operators() {
+    let mut stat = LayeredStat::new(10);
+    stat += 5;
+    stat -= 1;
+    stat *= 4.0;
+    stat /= 2.0;
+    assert_eq!(stat, LayeredStat::new(10).with_flat(4).with_more(2.0));
+}
+
+#[test]
+fn apply() {
+    let mut stat = LayeredStat::new(10);
+    stat.apply(Modifier::new(2, 2.0));
+    assert_eq!(stat, LayeredStat::new(10).with_flat(2).with_more(2.0));
+}
+
+#[test]
+fn increased_sums() {
+    let mut stat = LayeredStat::new(100);
+    stat.multiply_in(MultiplierLayer::Increased, 1.1);
+    stat.multiply_in(MultiplierLayer::Increased, 1.1);
+    assert_eq!(stat.total(), 120);
+}
+
+#[test]
+fn more_compounds() {
+    let mut stat = LayeredStat::new(100.0);
+    stat.multiply_in(MultiplierLayer::More, 1.5);
+    stat.multiply_in(MultiplierLayer::More, 1.5);
+    assert_eq!(stat.total(), 225.0);
+}
+
+#[test]
+fn final_flat_after_multipliers() {
+    let mut stat = LayeredStat::new(10);
+    stat.add_to(FlatLayer::FinalFlat, 5);
+    stat *= 2.0;
+    assert_eq!(stat, LayeredStat::new(10).with_final_flat(5).with_more(2.0));
+    assert_eq!(stat.total(), 25);
+}
+
+#[test]
+fn apply_to() {
+    let mut stat = LayeredStat::new(100);
+    stat.apply_to(
+        FlatLayer::FinalFlat,
+        MultiplierLayer::Increased,
+        Modifier::new(5, 1.2),
+    );
+    stat.apply_to(
+        FlatLayer::Flat,
+        MultiplierLayer::Increased,
+        Modifier::new(10, 1.3),
+    );
+
+    assert_eq!(
+        stat,
+        LayeredStat::new(100)
+            .with_flat(10)
+            .with_increased(0.5)
+            .with_final_flat(5)
+    );
+    assert_eq!(stat.total(), 170);
+}
+
+#[test]
+fn apply_to_final_base_percent() {
+    let mut stat = LayeredStat::new(100);
+    stat *= 2.0;
+    stat.apply_to(
+        FlatLayer::FinalFlat,
+        MultiplierLayer::More,
+        Modifier::from_base_percent(10.0),
+    );
+
+    // The percentage is added after multipliers, so it isn't doubled.
+    assert_eq!(stat.total(), 210);
+    assert_eq!(stat.to_string(), "((100 + 0) x (1 + 0) x 2) + 0 + 10%");
+
+    stat.reset_modifiers();
+    assert_eq!(stat, LayeredStat::new(100));
+}
+
+#[test]
+fn display() {
+    let stat = LayeredStat::new(10)
+        .with_flat(5)
+        .with_increased(0.5)
+        .with_final_flat(1);
+    assert_eq!(stat.to_string(), "((10 + 5) x (1 + 0.5) x 1) + 1");
+}
+
+#[test]
+fn total_rounding() {
+    let stat: LayeredStat = LayeredStat::new(15).with_more(0.99);
+    assert_eq!(stat.total(), 14);
+    assert_eq!(stat.with_rounding(Rounding::HalfAway).total(), 15);
+    assert!((stat.total_exact() - 14.85).abs() < 0.001);
+}
+
+#[test]
+fn apply_base_percent() {
+    let mut stat = LayeredStat::new(200);
+    stat.apply(Modifier::from_base_percent(10.0));
+    stat.apply(Modifier::from_base_percent(5.0));
+    assert_eq!(stat.base_percent, 15.0);
+    assert_eq!(stat.total(), 230);
+    assert_eq!(stat.to_string(), "((200 + 0 + 15%) x (1 + 0) x 1) + 0");
+}
+
+#[test]
+fn base_percent_follows_base() {
+    let mut stat = LayeredStat::new(100);
+    stat.apply(Modifier::from_base_percent(10.0));
+    stat.base = 200;
+    assert_eq!(stat.total(), 220);
+    assert_eq!(stat.total_exact(), 220.0f64);
+
+    stat.reset_modifiers();
+    assert_eq!(stat.total(), 200);
+}
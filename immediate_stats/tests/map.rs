@@ -0,0 +1,123 @@
+//! Tests the various methods of `StatMap`.
+
+use immediate_stats::*;
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Copy, Clone)]
+enum Attribute {
+    Strength,
+    Agility,
+    Luck,
+}
+
+#[test]
+fn missing_keys_use_default() {
+    let stats = StatMap::<Attribute>::new(10).with_base(Attribute::Strength, 15);
+
+    assert_eq!(stats.total(&Attribute::Strength), 15);
+    assert_eq!(stats.total(&Attribute::Luck), 10);
+    assert_eq!(stats[&Attribute::Luck], Stat::new(10));
+    assert!(!stats.contains_key(&Attribute::Luck));
+}
+
+#[test]
+fn default_is_copied() {
+    let mut stats = StatMap::<Attribute>::from(Stat::new(10).with_bounds(0, 20));
+    stats.apply(Attribute::Agility, Modifier::from_multiplier(3.0));
+
+    assert_eq!(stats.total(&Attribute::Agility), 20);
+    assert_eq!(stats.len(), 1);
+}
+
+#[test]
+fn with_base_copies_default() {
+    let stats = StatMap::<Attribute>::from(Stat::new(10).with_bounds(0, 20))
+        .with_base(Attribute::Strength, 30);
+
+    assert_eq!(
+        stats[&Attribute::Strength],
+        Stat::new(30).with_bounds(0, 20)
+    );
+    assert_eq!(stats.total(&Attribute::Strength), 20);
+}
+
+#[test]
+fn index_mut_creates_entry() {
+    let mut stats = StatMap::<String>::new(10);
+    stats["speed"] += 5;
+    stats["speed"] *= 2.0;
+
+    assert_eq!(stats.total("speed"), 30);
+    assert_eq!(stats.keys().collect::<Vec<_>>(), ["speed"]);
+
+    let mut stats = StatMap::<Attribute>::new(10);
+    stats[&Attribute::Strength] -= 3;
+    assert_eq!(stats.total(&Attribute::Strength), 7);
+}
+
+#[test]
+fn reset_all() {
+    let mut stats = StatMap::<Attribute>::new(10)
+        .with_base(Attribute::Strength, 5)
+        .with_base(Attribute::Luck, 1);
+
+    for (_, stat) in &mut stats {
+        *stat += 100;
+    }
+    stats.default += 100;
+
+    stats.reset_modifiers();
+
+    assert_eq!(stats.total(&Attribute::Strength), 5);
+    assert_eq!(stats.total(&Attribute::Luck), 1);
+    assert_eq!(stats.default, Stat::new(10));
+}
+
+#[test]
+fn totals_in_key_order() {
+    let mut stats: StatMap<Attribute> = [
+        (Attribute::Luck, Stat::new(1)),
+        (Attribute::Strength, Stat::new(5)),
+    ]
+    .into_iter()
+    .collect();
+
+    stats.apply(Attribute::Agility, Modifier::from_bonus(3));
+
+    assert_eq!(
+        stats.totals().collect::<Vec<_>>(),
+        [
+            (&Attribute::Strength, 5),
+            (&Attribute::Agility, 3),
+            (&Attribute::Luck, 1)
+        ]
+    );
+}
+
+#[test]
+fn remove() {
+    let mut stats = StatMap::<String>::new(10).with_base("speed".to_string(), 3);
+    assert_eq!(stats.remove("speed"), Some(Stat::new(3)));
+    assert!(stats.is_empty());
+}
+
+#[test]
+fn display() {
+    let mut stats = StatMap::<String>::new(10).with_base("speed".to_string(), 3);
+    stats["armor"] += 2;
+
+    assert_eq!(
+        stats.to_string(),
+        "{armor: (10 + 2) x 1, speed: (3 + 0) x 1}"
+    );
+}
+
+#[test]
+fn reset_custom_key() {
+    #[derive(PartialEq, Eq, PartialOrd, Ord, Debug)]
+    struct Key;
+
+    let mut stats = StatMap::<Key>::new(10);
+    stats.apply(Key, Modifier::from_bonus(5));
+    stats.reset_modifiers();
+    assert_eq!(stats.total(&Key), 10);
+}
@@ -11,6 +11,7 @@ fn add() {
         Modifier {
             bonus: 5,
             multiplier: 1.0,
+            ..Default::default()
         }
     );
 }
@@ -24,47 +25,212 @@ fn subtract() {
         Modifier {
             bonus: -5,
             multiplier: 1.0,
+            ..Default::default()
         }
     );
 }
 
 #[test]
 fn multiply() {
-    let mut modifier = Modifier::default();
+    let mut modifier: Modifier = Modifier::default();
     modifier *= 2.0;
     assert_eq!(
         modifier,
         Modifier {
             bonus: 0,
             multiplier: 2.0,
+            ..Default::default()
         }
     );
 }
 
 #[test]
 fn divide() {
-    let mut modifier = Modifier::default();
+    let mut modifier: Modifier = Modifier::default();
     modifier /= 2.0;
     assert_eq!(
         modifier,
         Modifier {
             bonus: 0,
             multiplier: 0.5,
+            ..Default::default()
         }
     );
 }
 
 #[test]
 fn scaled() {
-    let modifier = Modifier {
+    let modifier: Modifier = Modifier {
         bonus: 10,
         multiplier: 3.0,
+        ..Default::default()
     };
     assert_eq!(
         modifier.scaled(0.5),
         Modifier {
             bonus: 5,
             multiplier: 2.0,
+            ..Default::default()
+        }
+    );
+}
+
+#[test]
+fn scaled_float() {
+    let modifier: Modifier<f32> = Modifier {
+        bonus: 3.0,
+        multiplier: 3.0,
+        ..Default::default()
+    };
+    assert_eq!(
+        modifier.scaled(0.5),
+        Modifier {
+            bonus: 1.5,
+            multiplier: 2.0,
+            ..Default::default()
         }
     );
 }
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "multiplier must be finite")]
+fn multiply_infinity_panics() {
+    let mut modifier: Modifier = Modifier::default();
+    modifier *= f32::INFINITY;
+}
+
+#[test]
+fn scaled_base_percent() {
+    let modifier = Modifier::<i32>::from_base_percent(20.0);
+    assert_eq!(modifier.scaled(0.5), Modifier::from_base_percent(10.0));
+    assert_eq!(modifier.scaled(0.0), Modifier::default());
+}
+
+#[test]
+fn display() {
+    assert_eq!(Modifier::<i32>::new(5, 2.0).to_string(), "(+5) x 2");
+
+    let modifier: Modifier = Modifier {
+        bonus: 5,
+        base_percent: 10.0,
+        multiplier: 2.0,
+    };
+    assert_eq!(modifier.to_string(), "(+5 +10%) x 2");
+}
+
+#[test]
+fn mul_matches_apply() {
+    let a = Modifier::new(5, 1.5);
+    let b = Modifier::new(3, 2.0);
+
+    let mut applied = Stat::new(10);
+    applied.apply(a);
+    applied.apply(b);
+
+    let mut combined = Stat::new(10);
+    combined.apply(a * b);
+
+    assert_eq!(applied, combined);
+    assert_eq!(a * b, Modifier::new(8, 3.0));
+}
+
+#[test]
+fn add_sums_multipliers() {
+    let a: Modifier = Modifier::new(5, 1.1);
+    let b = Modifier::new(3, 1.1);
+    let sum = a + b;
+
+    assert_eq!(sum.bonus, 8);
+    assert!((sum.multiplier - 1.2).abs() < 1e-6);
+}
+
+#[test]
+fn sum_and_product() {
+    let modifiers = vec![
+        Modifier::new(1, 2.0),
+        Modifier::new(2, 3.0),
+        Modifier::from_base_percent(10.0),
+    ];
+
+    let sum: Modifier = modifiers.iter().sum();
+    assert_eq!(
+        sum,
+        Modifier {
+            bonus: 3,
+            base_percent: 10.0,
+            multiplier: 4.0,
+        }
+    );
+
+    let product: Modifier = modifiers.iter().product();
+    assert_eq!(product.multiplier, 6.0);
+
+    let collected: Modifier = modifiers.into_iter().collect();
+    assert_eq!(collected, product);
+}
+
+#[test]
+fn empty_iterators_are_identity() {
+    assert!(
+        std::iter::empty::<Modifier>()
+            .sum::<Modifier>()
+            .is_identity()
+    );
+    assert!(
+        std::iter::empty::<Modifier>()
+            .product::<Modifier>()
+            .is_identity()
+    );
+}
+
+#[test]
+fn inverse() {
+    let modifier: Modifier = Modifier {
+        bonus: 5,
+        base_percent: 10.0,
+        multiplier: 4.0,
+    };
+    let inverse = modifier.inverse();
+
+    assert_eq!(
+        inverse,
+        Modifier {
+            bonus: -5,
+            base_percent: -10.0,
+            multiplier: 0.25,
+        }
+    );
+    assert!((modifier * inverse).is_identity());
+}
+
+#[test]
+fn is_identity() {
+    assert!(Modifier::<i32>::default().is_identity());
+    assert!(!Modifier::<i32>::from_bonus(1).is_identity());
+    assert!(!Modifier::<i32>::from_multiplier(0.5).is_identity());
+}
+
+#[test]
+fn stronger_than() {
+    let weak: Modifier = Modifier::new(5, 1.5);
+    let strong = Modifier::new(10, 1.5);
+
+    assert!(strong > weak);
+    assert!(weak < strong);
+    assert!(weak <= weak);
+
+    // A higher bonus but lower multiplier is neither stronger nor weaker.
+    let mixed = Modifier::new(20, 1.0);
+    assert_eq!(mixed.partial_cmp(&strong), None);
+}
+
+#[test]
+fn cmp_at() {
+    let flat = Modifier::from_bonus(20);
+    let percent = Modifier::from_multiplier(2.0);
+
+    assert_eq!(flat.cmp_at(&percent, 10), Some(std::cmp::Ordering::Greater));
+    assert_eq!(flat.cmp_at(&percent, 20), Some(std::cmp::Ordering::Equal));
+    assert_eq!(flat.cmp_at(&percent, 100), Some(std::cmp::Ordering::Less));
+}
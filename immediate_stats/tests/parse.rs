@@ -0,0 +1,198 @@
+//! Tests parsing stats and modifiers from text.
+
+use immediate_stats::*;
+use proptest::prelude::*;
+
+#[test]
+fn parse_modifier_display() {
+    assert_eq!("(+5) x 2".parse(), Ok(Modifier::<i32>::new(5, 2.0)));
+    assert_eq!(
+        "(+5 +10%) x 2".parse(),
+        Ok(Modifier::<i32> {
+            bonus: 5,
+            base_percent: 10.0,
+            multiplier: 2.0,
+        })
+    );
+}
+
+#[test]
+fn parse_modifier_short() {
+    assert_eq!("+5".parse(), Ok(Modifier::<i32>::from_bonus(5)));
+    assert_eq!("x1.5".parse(), Ok(Modifier::<i32>::from_multiplier(1.5)));
+    assert_eq!(
+        "-20%".parse(),
+        Ok(Modifier::<i32>::from_base_percent(-20.0))
+    );
+    assert_eq!("+5 x1.5".parse(), Ok(Modifier::<i32>::new(5, 1.5)));
+    assert_eq!("x 1.5 - 2".parse(), Ok(Modifier::<i32>::new(-2, 1.5)));
+}
+
+#[test]
+fn parse_stat_display() {
+    assert_eq!(
+        "(10 + 5) x 2".parse(),
+        Ok(Stat::new(10).with_modifier(Modifier::new(5, 2.0)))
+    );
+    assert_eq!("(10 + -2) x 1".parse(), Ok(Stat::new(10).with_bonus(-2)));
+    assert_eq!(
+        "(10 + 0) x 1 [0..=20]".parse(),
+        Ok(Stat::new(10).with_bounds(0, 20))
+    );
+    assert_eq!(
+        "(10 + 0) x 1 [..=20]".parse(),
+        Ok(Stat::new(10).with_max(20))
+    );
+    assert_eq!(
+        "(0.5 + 0) x 1 [0.25..]".parse(),
+        Ok(Stat::new(0.5).with_min(0.25))
+    );
+}
+
+#[test]
+fn parse_stat_short() {
+    assert_eq!("10".parse(), Ok(Stat::new(10)));
+    assert_eq!(
+        "10 +5 x1.5".parse(),
+        Ok(Stat::new(10).with_modifier(Modifier::new(5, 1.5)))
+    );
+}
+
+#[test]
+fn parse_stat_extras() {
+    let mut stat = Stat::new(10)
+        .with_override(Override::new(3, 2))
+        .with_persistent(Modifier::new(5, 2.0));
+    stat.rounding = Rounding::Floor;
+
+    let text = stat.to_string();
+    assert_eq!(
+        text,
+        "(10 + 0) x 1 {rounding: Floor} {persistent: (+5) x 2} = 3 (override, priority 2)"
+    );
+    assert_eq!(text.parse(), Ok(stat));
+}
+
+#[test]
+fn parse_errors() {
+    let error = "(+5 x 2".parse::<Modifier>().unwrap_err();
+    assert_eq!(error.position(), 7);
+    assert_eq!(
+        error.to_string(),
+        "expected `)`, but found the end of the input at position 7"
+    );
+
+    let error = "+5.5".parse::<Modifier>().unwrap_err();
+    assert_eq!(error.position(), 1);
+    assert_eq!(
+        error.kind(),
+        &ParseStatErrorKind::InvalidNumber("5.5".into())
+    );
+
+    let error = "+5 x2 +3".parse::<Modifier>().unwrap_err();
+    assert_eq!(error.position(), 6);
+    assert_eq!(
+        error.to_string(),
+        "a bonus was given more than once at position 6"
+    );
+
+    let error = "10 {rounding: Up}".parse::<Stat>().unwrap_err();
+    assert_eq!(error.position(), 14);
+    assert_eq!(
+        error.kind(),
+        &ParseStatErrorKind::InvalidRounding("Up".into())
+    );
+
+    let error = "".parse::<Modifier>().unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "expected a bonus, percentage or multiplier, but found the end of the input at position 0"
+    );
+
+    let error = "10 ?".parse::<Stat>().unwrap_err();
+    assert_eq!(
+        error.kind(),
+        &ParseStatErrorKind::Expected {
+            expected: "the end of the input",
+            found: Some('?'),
+        }
+    );
+}
+
+fn finite() -> impl Strategy<Value = f32> + Clone {
+    any::<f32>().prop_filter("must be finite", |float| float.is_finite())
+}
+
+fn rounding() -> impl Strategy<Value = Rounding> {
+    prop_oneof![
+        Just(Rounding::Truncate),
+        Just(Rounding::Floor),
+        Just(Rounding::Ceil),
+        Just(Rounding::HalfEven),
+        Just(Rounding::HalfAway),
+    ]
+}
+
+fn modifier<T: StatValue>(value: impl Strategy<Value = T>) -> impl Strategy<Value = Modifier<T>> {
+    (value, finite(), finite()).prop_map(|(bonus, base_percent, multiplier)| Modifier {
+        bonus: bonus.to_bonus(),
+        base_percent,
+        multiplier,
+    })
+}
+
+fn stat<T: StatValue>(value: impl Strategy<Value = T> + Clone) -> impl Strategy<Value = Stat<T>> {
+    (
+        (value.clone(), value.clone(), finite(), finite()),
+        (
+            proptest::option::of(value.clone()),
+            proptest::option::of(value.clone()),
+            rounding(),
+        ),
+        proptest::option::of((value.clone(), any::<i32>())),
+        modifier(value),
+    )
+        .prop_map(
+            |(
+                (base, bonus, base_percent, multiplier),
+                (min, max, rounding),
+                overridden,
+                persistent,
+            )| {
+                Stat {
+                    base,
+                    bonus: bonus.to_bonus(),
+                    base_percent,
+                    multiplier,
+                    min,
+                    max,
+                    rounding,
+                    overridden: overridden.map(|(value, priority)| Override::new(value, priority)),
+                    persistent,
+                    ..Default::default()
+                }
+            },
+        )
+}
+
+proptest! {
+    #[test]
+    fn modifier_round_trip_int(modifier in modifier(any::<i32>())) {
+        prop_assert_eq!(modifier.to_string().parse(), Ok(modifier));
+    }
+
+    #[test]
+    fn modifier_round_trip_unsigned(modifier in modifier(any::<u8>())) {
+        prop_assert_eq!(modifier.to_string().parse(), Ok(modifier));
+    }
+
+    #[test]
+    fn stat_round_trip_int(stat in stat(any::<i64>())) {
+        prop_assert_eq!(stat.to_string().parse(), Ok(stat));
+    }
+
+    #[test]
+    fn stat_round_trip_float(stat in stat(finite())) {
+        prop_assert_eq!(stat.to_string().parse(), Ok(stat));
+    }
+}
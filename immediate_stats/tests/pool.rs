@@ -0,0 +1,143 @@
+//! Tests the various methods of `Pool`.
+
+use immediate_stats::*;
+
+#[test]
+fn new_is_full() {
+    let pool = Pool::new(100);
+    assert_eq!(pool.current(), 100);
+    assert!(pool.is_full());
+    assert!(!pool.is_empty());
+}
+
+#[test]
+fn damage() {
+    let mut pool = Pool::new(100);
+    assert_eq!(pool.damage(30), 30);
+    assert_eq!(pool.current(), 70);
+
+    assert_eq!(pool.damage(100), 70);
+    assert_eq!(pool.current(), 0);
+    assert!(pool.is_empty());
+}
+
+#[test]
+fn negative_amounts() {
+    let mut pool = Pool::new(100).with_current(50);
+    assert_eq!(pool.damage(-30), 0);
+    assert_eq!(pool.heal(-30), 0);
+    assert_eq!(pool.current(), 50);
+}
+
+#[test]
+fn damage_unsigned() {
+    let mut pool: Pool<u32> = Pool::new(10);
+    assert_eq!(pool.damage(50), 10);
+    assert_eq!(pool.current(), 0);
+}
+
+#[test]
+fn heal() {
+    let mut pool = Pool::new(100).with_current(50);
+    assert_eq!(pool.heal(20), 20);
+    assert_eq!(pool.current(), 70);
+
+    assert_eq!(pool.heal(100), 30);
+    assert_eq!(pool.current(), 100);
+}
+
+#[test]
+fn heal_against_modified_max() {
+    let mut pool = Pool::new(100);
+    pool.max *= 1.2;
+    pool.heal(50);
+    assert_eq!(pool.current(), 120);
+}
+
+#[test]
+fn with_current_is_clamped() {
+    assert_eq!(Pool::new(100).with_current(150).current(), 100);
+    assert_eq!(Pool::new(100).with_current(-5).current(), 0);
+}
+
+#[test]
+fn keep_current() {
+    let mut pool = Pool::new(100).with_current(50);
+
+    pool.max *= 1.2;
+    assert_eq!(pool.current(), 50);
+
+    pool.max *= 0.25;
+    assert_eq!(pool.current(), 30);
+}
+
+#[test]
+fn keep_ratio() {
+    let mut pool = Pool::new(100)
+        .with_policy(PoolPolicy::KeepRatio)
+        .with_current(50);
+
+    pool.max *= 1.2;
+    assert_eq!(pool.current(), 60);
+    assert_eq!(pool.to_string(), "60 / (100 + 0) x 1.2");
+
+    pool.reset_modifiers();
+    assert_eq!(pool.current(), 50);
+}
+
+#[test]
+fn keep_ratio_over_many_frames() {
+    let mut pool = Pool::new(100)
+        .with_policy(PoolPolicy::KeepRatio)
+        .with_current(33);
+
+    for _ in 0..20 {
+        pool.reset_modifiers();
+        pool.max *= 1.15;
+        assert_eq!(pool.current(), 37);
+    }
+
+    pool.reset_modifiers();
+    assert_eq!(pool.current(), 33);
+}
+
+#[test]
+fn reset_never_exceeds_max() {
+    let mut pool = Pool::new(100);
+    pool.max += 50;
+    pool.fill();
+    assert_eq!(pool.current(), 150);
+
+    pool.reset_modifiers();
+    assert_eq!(pool.current(), 100);
+    assert_eq!(pool.max, Stat::new(100));
+}
+
+#[test]
+fn reset_keeps_ratio() {
+    let mut pool = Pool::new(100).with_policy(PoolPolicy::KeepRatio);
+    pool.max *= 2.0;
+    pool.damage(100);
+
+    pool.reset_modifiers();
+    assert_eq!(pool.current(), 50);
+}
+
+#[derive(StatContainer)]
+struct Vitals {
+    #[stat]
+    health: Pool,
+}
+
+#[test]
+fn derive() {
+    let mut vitals = Vitals {
+        health: Pool::new(100),
+    };
+
+    vitals.health.max += 20;
+    vitals.health.fill();
+    vitals.reset_modifiers();
+
+    assert_eq!(vitals.health.current(), 100);
+}
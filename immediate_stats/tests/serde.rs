@@ -0,0 +1,104 @@
+//! Tests serializing stats and modifiers, both with and without temporary modifiers.
+#![cfg(feature = "serde")]
+
+use immediate_stats::*;
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn stat_round_trip() {
+    let mut stat = Stat::new(10).with_bounds(0, 100);
+    stat += 5;
+    stat *= 1.5;
+
+    let json = serde_json::to_string(&stat).unwrap();
+    let loaded: Stat = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(loaded, stat);
+}
+
+#[test]
+fn modifier_round_trip() {
+    let modifier = Modifier::new(5.5, 2.0);
+
+    let json = serde_json::to_string(&modifier).unwrap();
+    assert_eq!(json, r#"{"bonus":5.5,"base_percent":0.0,"multiplier":2.0}"#);
+
+    let loaded: Modifier<f32> = serde_json::from_str(&json).unwrap();
+    assert_eq!(loaded, modifier);
+}
+
+#[test]
+fn vector_round_trip() {
+    let mut stat = Vec3Stat::new([1.0, 2.0, 3.0]);
+    stat *= [2.0, 1.0, 0.5];
+
+    let json = serde_json::to_string(&stat).unwrap();
+    let loaded: Vec3Stat = serde_json::from_str(&json).unwrap();
+    assert_eq!(loaded, stat);
+
+    let error = serde_json::from_str::<Vec2Stat>(&json).unwrap_err();
+    assert!(error.to_string().contains("expected 2 axes, but found 3"));
+}
+
+#[derive(StatContainer, Serialize, Deserialize, PartialEq, Debug)]
+struct Character {
+    #[serde(with = "immediate_stats::skip_temporary")]
+    speed: Stat,
+    #[serde(with = "immediate_stats::skip_temporary")]
+    gravity: Stat<f32>,
+    health: Pool,
+    name: String,
+}
+
+#[test]
+fn derived_container() {
+    let mut character = Character {
+        speed: Stat::new(10),
+        gravity: Stat::new(9.8),
+        health: Pool::new(100),
+        name: "Player".to_string(),
+    };
+
+    character.speed *= 2.0;
+    character.gravity += 1.0;
+    character.health.damage(30);
+
+    let json = serde_json::to_value(&character).unwrap();
+    assert_eq!(json["speed"], serde_json::json!({ "base": 10 }));
+    assert_eq!(json["name"], "Player");
+
+    let loaded: Character = serde_json::from_value(json).unwrap();
+    character.reset_modifiers();
+    assert_eq!(loaded.speed, character.speed);
+    assert_eq!(loaded.gravity, character.gravity);
+    assert_eq!(loaded.health.current(), 70);
+}
+
+#[derive(Serialize, Deserialize)]
+struct Saved {
+    #[serde(with = "immediate_stats::skip_temporary")]
+    stat: Stat,
+}
+
+#[test]
+fn skip_temporary_keeps_settings() {
+    let mut stat = Stat::new(10)
+        .with_bounds(0, 100)
+        .with_rounding(Rounding::HalfAway);
+    let _ring = stat.add_persistent(Modifier::from_bonus(5)).unwrap();
+    stat += 20;
+    stat *= 2.0;
+
+    let json = serde_json::to_string(&Saved { stat }).unwrap();
+    let loaded: Saved = serde_json::from_str(&json).unwrap();
+
+    stat.reset_modifiers();
+    assert_eq!(loaded.stat, stat);
+    assert_eq!(loaded.stat.total(), 15);
+}
+
+#[test]
+fn skip_temporary_defaults() {
+    let loaded: Saved = serde_json::from_str(r#"{ "stat": { "base": 10 } }"#).unwrap();
+    assert_eq!(loaded.stat, Stat::new(10));
+}
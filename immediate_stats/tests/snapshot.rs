@@ -0,0 +1,63 @@
+//! Tests the various methods of `SnapshotStat`.
+
+use immediate_stats::*;
+
+#[test]
+fn previous_total_starts_at_total() {
+    let stat = SnapshotStat::new(10);
+    assert_eq!(stat.previous_total(), 10);
+    assert!(!stat.changed());
+    assert_eq!(stat.delta(), 0.0f64);
+}
+
+#[test]
+fn reset_records_total() {
+    let mut stat = SnapshotStat::new(10);
+    stat += 5;
+    stat *= 2.0;
+    stat.reset_modifiers();
+
+    assert_eq!(stat.previous_total(), 30);
+    assert_eq!(stat.stat, Stat::new(10));
+}
+
+#[test]
+fn changed() {
+    let mut stat = SnapshotStat::new(10);
+
+    stat *= 0.5;
+    assert!(stat.changed());
+    assert_eq!(stat.delta(), -5.0f64);
+
+    stat.reset_modifiers();
+    stat *= 0.5;
+    assert!(!stat.changed());
+
+    stat.reset_modifiers();
+    assert!(stat.changed());
+    assert_eq!(stat.delta(), 5.0f64);
+}
+
+#[test]
+fn delta_unsigned() {
+    let mut stat: SnapshotStat<u32> = SnapshotStat::new(10);
+    stat.reset_modifiers();
+    stat *= 0.6;
+    assert_eq!(stat.delta(), -4.0f64);
+}
+
+#[derive(StatContainer)]
+struct Movement {
+    speed: SnapshotStat,
+}
+
+#[test]
+fn derive() {
+    let mut movement = Movement {
+        speed: SnapshotStat::new(10),
+    };
+
+    movement.speed += 5;
+    movement.reset_modifiers();
+    assert_eq!(movement.speed.previous_total(), 15);
+}
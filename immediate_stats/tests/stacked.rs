@@ -0,0 +1,157 @@
+//! Tests the various methods of `StackedStat`.
+
+use immediate_stats::*;
+
+#[test]
+fn reset() {
+    let mut stat: StackedStat = StackedStat::new(10);
+    stat += 5;
+    stat.apply_stacking(
+        "aura",
+        "ally",
+        StackingRule::Unique,
+        Modifier::from_bonus(2),
+    );
+    stat.reset_modifiers();
+
+    assert_eq!(stat, StackedStat::new(10));
+    assert_eq!(stat.count(&"aura"), 0);
+}
+
+#[test]
+fn unique() {
+    let mut stat: StackedStat = StackedStat::new(10);
+
+    for ally in ["paladin", "cleric", "bard"] {
+        stat.apply_stacking("aura", ally, StackingRule::Unique, Modifier::from_bonus(5));
+    }
+
+    assert_eq!(stat.count(&"aura"), 1);
+    assert_eq!(stat.total(), 15);
+}
+
+#[test]
+fn capped() {
+    let mut stat: StackedStat<i32, &str, u32> = StackedStat::new(100);
+
+    for spider in 0..8 {
+        stat.apply_stacking(
+            "poison",
+            spider,
+            StackingRule::Capped(5),
+            Modifier::from_bonus(-10),
+        );
+    }
+
+    assert_eq!(stat.count(&"poison"), 5);
+    assert_eq!(stat.total(), 50);
+}
+
+#[test]
+fn unlimited() {
+    let mut stat: StackedStat<i32, &str, u32> = StackedStat::new(0);
+
+    for enemy in 0..8 {
+        stat.apply_stacking(
+            "rage",
+            enemy,
+            StackingRule::Unlimited,
+            Modifier::from_bonus(1),
+        );
+    }
+
+    assert_eq!(stat.count(&"rage"), 8);
+    assert_eq!(stat.total(), 8);
+}
+
+#[test]
+fn unique_same_source() {
+    let mut stat: StackedStat = StackedStat::new(10);
+    let rule = StackingRule::Unique;
+
+    assert!(stat.apply_stacking("aura", "paladin", rule, Modifier::from_bonus(5)));
+    assert!(!stat.apply_stacking("aura", "paladin", rule, Modifier::from_bonus(5)));
+
+    assert_eq!(stat.count(&"aura"), 1);
+    assert_eq!(stat.total(), 15);
+}
+
+#[test]
+fn capped_same_source() {
+    let mut stat: StackedStat = StackedStat::new(100);
+    let rule = StackingRule::Capped(5);
+
+    assert!(stat.apply_stacking("poison", "spider", rule, Modifier::from_bonus(-10)));
+    assert!(!stat.apply_stacking("poison", "spider", rule, Modifier::from_bonus(-10)));
+    assert!(stat.apply_stacking("poison", "snake", rule, Modifier::from_bonus(-10)));
+
+    assert_eq!(stat.count(&"poison"), 2);
+    assert_eq!(stat.total(), 80);
+}
+
+#[test]
+fn unlimited_same_source() {
+    let mut stat: StackedStat = StackedStat::new(0);
+    let rule = StackingRule::Unlimited;
+
+    assert!(stat.apply_stacking("rage", "goblin", rule, Modifier::from_bonus(1)));
+    assert!(!stat.apply_stacking("rage", "goblin", rule, Modifier::from_bonus(1)));
+
+    assert_eq!(stat.count(&"rage"), 1);
+    assert_eq!(stat.total(), 1);
+}
+
+#[test]
+fn same_source_after_reset() {
+    let mut stat: StackedStat = StackedStat::new(0);
+    let rule = StackingRule::Unlimited;
+
+    assert!(stat.apply_stacking("rage", "goblin", rule, Modifier::from_bonus(1)));
+    stat.reset_modifiers();
+    assert!(stat.apply_stacking("rage", "goblin", rule, Modifier::from_bonus(1)));
+    assert_eq!(stat.total(), 1);
+}
+
+#[test]
+fn separate_keys() {
+    let mut stat: StackedStat = StackedStat::new(10);
+    stat.apply_stacking(
+        "fire",
+        "mage",
+        StackingRule::Unique,
+        Modifier::from_bonus(1),
+    );
+    stat.apply_stacking("ice", "mage", StackingRule::Unique, Modifier::from_bonus(2));
+
+    assert_eq!(stat.stacks().len(), 2);
+    assert_eq!(stat.stacks()[0].sources, ["mage"]);
+    assert_eq!(stat.total(), 13);
+}
+
+#[test]
+fn first_rule_is_kept() {
+    let mut stat: StackedStat = StackedStat::new(10);
+    stat.apply_stacking(
+        "aura",
+        "paladin",
+        StackingRule::Unique,
+        Modifier::from_bonus(1),
+    );
+    stat.apply_stacking(
+        "aura",
+        "cleric",
+        StackingRule::Unlimited,
+        Modifier::from_bonus(1),
+    );
+
+    assert_eq!(stat.total(), 11);
+}
+
+#[test]
+fn display() {
+    let mut stat: StackedStat = StackedStat::new(10);
+    let rule = StackingRule::Capped(5);
+    stat.apply_stacking("poison", "spider", rule, Modifier::from_bonus(-1));
+    stat.apply_stacking("poison", "snake", rule, Modifier::from_bonus(-1));
+    assert_eq!(stat.to_string(), "(10 + -2) x 1 {poison x2}");
+}
@@ -9,6 +9,7 @@ fn reset() {
             base: i,
             bonus: 4,
             multiplier: 1.5,
+            ..Default::default()
         };
         stat.reset_modifiers();
 
@@ -26,6 +27,7 @@ fn add() {
             base: 10,
             bonus: 5,
             multiplier: 1.0,
+            ..Default::default()
         }
     );
 }
@@ -40,6 +42,7 @@ fn subtract() {
             base: 10,
             bonus: -5,
             multiplier: 1.0,
+            ..Default::default()
         }
     );
 }
@@ -54,6 +57,7 @@ fn multiply() {
             base: 10,
             bonus: 0,
             multiplier: 2.0,
+            ..Default::default()
         }
     );
 }
@@ -68,6 +72,7 @@ fn divide() {
             base: 10,
             bonus: 0,
             multiplier: 0.5,
+            ..Default::default()
         }
     );
 }
@@ -86,6 +91,7 @@ fn total() {
             base: 10,
             bonus: 4,
             multiplier: 1.5,
+            ..Default::default()
         }
         .total(),
         21
@@ -120,6 +126,7 @@ fn apply() {
             base: 10,
             bonus: 5,
             multiplier: 8.0,
+            ..Default::default()
         }
     )
 }
@@ -140,6 +147,439 @@ fn apply_scaled() {
             base: 10,
             bonus: 3,
             multiplier: 4.5,
+            ..Default::default()
         }
     )
 }
+
+#[test]
+fn total_float() {
+    let mut stat = Stat::new(4.5);
+    stat += 0.5;
+    stat *= 1.5;
+    assert_eq!(stat.total(), 7.5);
+}
+
+#[test]
+fn total_wide_integer() {
+    let mut stat: Stat<i64> = Stat::new(5_000_000_000);
+    stat += 1;
+    stat *= 2.0;
+    assert_eq!(stat.total(), 10_000_000_002);
+}
+
+#[test]
+fn total_unsigned() {
+    let mut stat: Stat<u32> = Stat::new(10);
+    stat.apply(Modifier::new(5, 0.5));
+    assert_eq!(stat.total(), 7);
+}
+
+#[test]
+fn large_base() {
+    let gold: Stat<u32> = Stat::new(4_000_000_001);
+    assert_eq!(gold.total(), 4_000_000_001);
+    assert_eq!(gold.checked_total(), Ok(4_000_000_001));
+
+    let stat = Stat::new(16_777_217);
+    assert_eq!(stat.total(), 16_777_217);
+    assert_eq!(stat.with_multiplier(2.0).total(), 33_554_434);
+
+    // Multipliers keep their decimal value when widened, so `x1.3` doesn't truncate to 12.
+    assert_eq!(Stat::new(10).with_multiplier(1.3).total(), 13);
+}
+
+#[test]
+fn debuff_unsigned() {
+    let mut stat: Stat<u32> = Stat::new(10);
+    stat -= 5;
+    assert_eq!(stat.bonus, -5);
+    assert_eq!(stat.total(), 5);
+
+    // Debuffs larger than the base saturate at zero, unless a percentage brings it back up.
+    stat -= 10;
+    assert_eq!(stat.total(), 0);
+    assert_eq!(stat.checked_total(), Err(StatError::TotalOverflow));
+    stat.apply(Modifier::from_base_percent(100.0));
+    assert_eq!(stat.total(), 5);
+
+    let mut stat: Stat<u8> = Stat::new(u8::MAX);
+    stat.apply(Modifier::from_bonus(-255).inverse());
+    assert_eq!(stat.saturating_total(), u8::MAX);
+}
+
+#[test]
+fn reset_float() {
+    let mut stat = Stat::new(1.25).with_modifier(Modifier::new(0.5, 2.0));
+    stat.reset_modifiers();
+    assert_eq!(stat, Stat::new(1.25));
+}
+
+#[test]
+fn display() {
+    assert_eq!(Stat::new(10).with_bonus(5).to_string(), "(10 + 5) x 1");
+    assert_eq!(
+        format!("{:.1}", Stat::new(4.5).with_multiplier(2.0)),
+        "(4.5 + 0) x 2.0"
+    );
+}
+
+#[test]
+fn total_clamped() {
+    let mut stat = Stat::new(10).with_bounds(0, 100);
+
+    stat -= 20;
+    assert_eq!(stat.total(), 0);
+
+    stat += 20;
+    stat *= 20.0;
+    assert_eq!(stat.total(), 100);
+}
+
+#[test]
+fn total_clamped_float() {
+    let stat = Stat::new(0.5).with_max(1.0).with_multiplier(3.0);
+    assert_eq!(stat.total(), 1.0);
+}
+
+#[test]
+fn reset_keeps_bounds() {
+    let mut stat = Stat::new(10).with_min(5).with_bonus(-20);
+    stat.reset_modifiers();
+    assert_eq!(stat, Stat::new(10).with_min(5));
+}
+
+#[test]
+fn display_bounds() {
+    assert_eq!(Stat::new(10).with_min(0).to_string(), "(10 + 0) x 1 [0..]");
+    assert_eq!(
+        Stat::new(10).with_max(20).to_string(),
+        "(10 + 0) x 1 [..=20]"
+    );
+    assert_eq!(
+        format!("{:.1}", Stat::new(10).with_bounds(0, 20)),
+        "(10 + 0) x 1.0 [0..=20]"
+    );
+}
+
+#[test]
+fn total_rounding() {
+    let stat = Stat::new(15).with_multiplier(0.99);
+
+    assert_eq!(stat.total(), 14);
+    assert_eq!(stat.with_rounding(Rounding::Truncate).total(), 14);
+    assert_eq!(stat.with_rounding(Rounding::Floor).total(), 14);
+    assert_eq!(stat.with_rounding(Rounding::Ceil).total(), 15);
+    assert_eq!(stat.with_rounding(Rounding::HalfEven).total(), 15);
+    assert_eq!(stat.with_rounding(Rounding::HalfAway).total(), 15);
+}
+
+#[test]
+fn total_rounding_negative() {
+    let stat = Stat::new(-5).with_multiplier(0.5);
+
+    assert_eq!(stat.total(), -2);
+    assert_eq!(stat.with_rounding(Rounding::Floor).total(), -3);
+    assert_eq!(stat.with_rounding(Rounding::Ceil).total(), -2);
+    assert_eq!(stat.with_rounding(Rounding::HalfEven).total(), -2);
+    assert_eq!(stat.with_rounding(Rounding::HalfAway).total(), -3);
+}
+
+#[test]
+fn total_rounding_float() {
+    let stat = Stat::new(2.5).with_rounding(Rounding::Floor);
+    assert_eq!(stat.total(), 2.5);
+}
+
+#[test]
+fn reset_keeps_rounding() {
+    let mut stat = Stat::new(10).with_rounding(Rounding::Ceil).with_bonus(5);
+    stat.reset_modifiers();
+    assert_eq!(stat, Stat::new(10).with_rounding(Rounding::Ceil));
+}
+
+#[test]
+fn total_exact() {
+    let stat = Stat::new(15).with_multiplier(0.5);
+    assert_eq!(stat.total_exact(), 7.5f64);
+    assert_eq!(stat.with_max(5).total_exact(), 5.0f64);
+
+    let wide: Stat<i64> = Stat::new(3).with_multiplier(0.5);
+    assert_eq!(wide.total_exact(), 1.5f64);
+}
+
+#[test]
+fn apply_scaled_rounding() {
+    let mut stat = Stat::new(10).with_rounding(Rounding::Ceil);
+    stat.apply_scaled(Modifier::from_bonus(3), 0.5);
+    assert_eq!(stat.bonus, 2);
+}
+
+#[test]
+fn checked_total() {
+    assert_eq!(Stat::new(10).with_multiplier(1.5).checked_total(), Ok(15));
+    assert_eq!(Stat::new(10).with_max(12).checked_total(), Ok(10));
+}
+
+#[test]
+fn checked_total_bonus_overflow() {
+    let stat = Stat::new(i32::MAX).with_bonus(1);
+    assert_eq!(stat.checked_total(), Err(StatError::BonusOverflow));
+}
+
+#[test]
+fn checked_total_invalid_multiplier() {
+    let stat = Stat::new(10).with_multiplier(f32::INFINITY);
+    assert_eq!(
+        stat.checked_total(),
+        Err(StatError::InvalidMultiplier(f32::INFINITY))
+    );
+}
+
+#[test]
+fn checked_total_overflow() {
+    let stat: Stat<u8> = Stat::new(200).with_multiplier(2.0);
+    assert_eq!(stat.checked_total(), Err(StatError::TotalOverflow));
+
+    let stat: Stat<u8> = Stat::new(255);
+    assert_eq!(stat.checked_total(), Ok(255));
+
+    let stat = Stat::new(-1.0f32)
+        .with_multiplier(f32::MAX)
+        .with_bonus(-1.0);
+    assert_eq!(stat.checked_total(), Err(StatError::TotalOverflow));
+}
+
+#[test]
+fn saturating_total() {
+    assert_eq!(
+        Stat::new(i32::MAX).with_bonus(1).saturating_total(),
+        i32::MAX
+    );
+    assert_eq!(
+        Stat::new(i32::MAX).with_multiplier(2.0).saturating_total(),
+        i32::MAX
+    );
+
+    let stat: Stat<u8> = Stat::new(5).with_bonus(i16::MAX);
+    assert_eq!(stat.saturating_total(), u8::MAX);
+}
+
+#[test]
+fn try_apply() {
+    let mut stat = Stat::new(10);
+    assert_eq!(stat.try_apply(Modifier::new(5, 2.0)), Ok(()));
+    assert_eq!(stat, Stat::new(10).with_modifier(Modifier::new(5, 2.0)));
+}
+
+#[test]
+fn try_apply_error_leaves_stat_unchanged() {
+    let mut stat = Stat::new(10).with_bonus(i32::MAX);
+    assert_eq!(
+        stat.try_apply(Modifier::new(1, 2.0)),
+        Err(StatError::BonusOverflow)
+    );
+    assert_eq!(
+        stat.try_apply(Modifier::from_multiplier(f32::NAN))
+            .map_err(|error| matches!(error, StatError::InvalidMultiplier(_))),
+        Err(true)
+    );
+    assert_eq!(stat, Stat::new(10).with_bonus(i32::MAX));
+}
+
+#[test]
+fn saturating_apply() {
+    let mut stat = Stat::new(10).with_bonus(i32::MAX);
+    stat.saturating_apply(Modifier::new(1, 2.0));
+    assert_eq!(
+        stat,
+        Stat::new(10).with_modifier(Modifier::new(i32::MAX, 2.0))
+    );
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "multiplier must be finite")]
+fn divide_by_zero_panics() {
+    let mut stat = Stat::new(10);
+    stat /= 0.0;
+}
+
+#[test]
+fn total_override() {
+    let mut stat = Stat::new(10).with_bounds(5, 20);
+    stat += 5;
+    stat *= 2.0;
+    stat.apply_override(Override::new(0, 0));
+
+    assert_eq!(stat.total(), 0);
+    assert_eq!(stat.checked_total(), Ok(0));
+    assert_eq!(stat.saturating_total(), 0);
+    assert_eq!(stat.total_exact(), 0.0f64);
+}
+
+#[test]
+fn override_priority() {
+    let mut stat = Stat::new(10);
+
+    stat.apply_override(Override::new(3, 1));
+    stat.apply_override(Override::new(0, 0));
+    assert_eq!(stat.total(), 3);
+
+    stat.apply_override(Override::new(5, 1));
+    assert_eq!(stat.total(), 5);
+
+    stat.apply_override(Override::new(7, 2));
+    assert_eq!(stat.total(), 7);
+}
+
+#[test]
+fn reset_clears_override() {
+    let mut stat = Stat::new(10).with_override(Override::new(0, 0));
+    stat.reset_modifiers();
+    assert_eq!(stat, Stat::new(10));
+    assert_eq!(stat.total(), 10);
+}
+
+#[test]
+fn display_override() {
+    let stat = Stat::new(10).with_override(Override::new(0, 0));
+    assert_eq!(stat.to_string(), "(10 + 0) x 1 = 0 (override)");
+}
+
+#[test]
+fn total_persistent() {
+    let mut stat = Stat::new(10);
+    let _sword = stat.add_persistent(Modifier::new(5, 2.0)).unwrap();
+    assert_eq!(stat.total(), 30);
+
+    stat += 5;
+    stat *= 1.5;
+    assert_eq!(stat.total(), 60); // (10 + 5 + 5) x 2 x 1.5
+    assert_eq!(stat.checked_total(), Ok(60));
+    assert_eq!(stat.saturating_total(), 60);
+    assert_eq!(stat.total_exact(), 60.0f64);
+}
+
+#[test]
+fn reset_keeps_persistent() {
+    let mut stat = Stat::new(10);
+    let _sword = stat.add_persistent(Modifier::new(5, 2.0)).unwrap();
+    stat += 5;
+    stat.reset_modifiers();
+
+    assert_eq!(stat.persistent, Modifier::new(5, 2.0));
+    assert_eq!(stat.total(), 30);
+}
+
+#[test]
+fn remove_persistent() {
+    let mut stat = Stat::new(10);
+    let sword = stat.add_persistent(Modifier::new(5, 2.0)).unwrap();
+    let ring = stat.add_persistent(Modifier::from_bonus(3)).unwrap();
+    assert_eq!(sword.modifier(), Modifier::new(5, 2.0));
+
+    assert_eq!(stat.remove_persistent(sword), Ok(()));
+    assert_eq!(stat.total(), 13);
+
+    assert_eq!(stat.remove_persistent(ring), Ok(()));
+    assert_eq!(stat, Stat::new(10));
+    assert_eq!(stat.to_string().parse(), Ok(stat));
+}
+
+#[test]
+fn add_persistent_invalid_multiplier() {
+    let mut stat = Stat::new(10);
+
+    for multiplier in [0.0, f32::INFINITY] {
+        assert_eq!(
+            stat.add_persistent(Modifier::from_multiplier(multiplier)),
+            Err(StatError::InvalidPersistentMultiplier(multiplier))
+        );
+    }
+
+    assert!(
+        stat.add_persistent(Modifier::from_multiplier(f32::NAN))
+            .is_err_and(|error| matches!(error, StatError::InvalidPersistentMultiplier(_)))
+    );
+    assert_eq!(stat, Stat::new(10));
+}
+
+#[test]
+fn remove_persistent_foreign_handle() {
+    let mut sword_stat = Stat::new(10);
+    let mut other = Stat::new(10);
+    let sword = sword_stat.add_persistent(Modifier::from_bonus(5)).unwrap();
+    let _ring = other.add_persistent(Modifier::from_bonus(3)).unwrap();
+
+    assert_eq!(
+        other.remove_persistent(sword),
+        Err(StatError::ForeignHandle)
+    );
+    assert_eq!(other.total(), 13);
+}
+
+#[test]
+fn remove_persistent_from_copy() {
+    let mut stat = Stat::new(10);
+    let sword = stat.add_persistent(Modifier::from_bonus(5)).unwrap();
+
+    let mut copy = stat;
+    assert_eq!(copy.remove_persistent(sword), Ok(()));
+    assert_eq!(copy.total(), 10);
+}
+
+#[test]
+fn display_persistent() {
+    let stat = Stat::new(10).with_persistent(Modifier::new(5, 2.0));
+    assert_eq!(stat.to_string(), "(10 + 0) x 1 {persistent: (+5) x 2}");
+}
+
+#[test]
+fn total_base_percent() {
+    let mut stat = Stat::new(200);
+    stat.apply(Modifier::from_base_percent(10.0));
+    stat += 5;
+    stat *= 2.0;
+    assert_eq!(stat.total(), 450); // (200 + 20 + 5) x 2
+    assert_eq!(stat.checked_total(), Ok(450));
+    assert_eq!(stat.saturating_total(), 450);
+
+    // The percent is resolved when the total is calculated.
+    stat.base = 100;
+    assert_eq!(stat.total(), 230);
+}
+
+#[test]
+fn persistent_base_percent() {
+    let mut stat = Stat::new(100);
+    let talent = stat
+        .add_persistent(Modifier::from_base_percent(10.0))
+        .unwrap();
+    stat.apply(Modifier::from_base_percent(5.0));
+    assert_eq!(stat.total(), 115);
+
+    stat.reset_modifiers();
+    assert_eq!(stat.total(), 110);
+
+    assert_eq!(stat.remove_persistent(talent), Ok(()));
+    assert_eq!(stat.total(), 100);
+}
+
+#[test]
+fn apply_scaled_base_percent() {
+    let mut stat = Stat::new(100);
+    stat.apply_scaled(Modifier::from_base_percent(20.0), 0.5);
+    assert_eq!(stat.base_percent, 10.0);
+    assert_eq!(stat.total(), 110);
+
+    stat.reset_modifiers();
+    assert_eq!(stat, Stat::new(100));
+}
+
+#[test]
+fn display_base_percent() {
+    let mut stat = Stat::new(10).with_modifier(Modifier::new(5, 2.0));
+    stat.apply(Modifier::from_base_percent(10.0));
+    assert_eq!(stat.to_string(), "(10 + 5 + 10%) x 2");
+}
@@ -0,0 +1,94 @@
+//! Tests the various methods of `TrackedStat`.
+
+use immediate_stats::*;
+
+#[test]
+fn reset() {
+    let mut stat: TrackedStat = TrackedStat::new(Stat::new(10));
+    stat.add("sword", 5);
+    stat *= 2.0;
+    stat.reset_modifiers();
+
+    assert_eq!(stat, TrackedStat::new(Stat::new(10)));
+    assert!(stat.contributions().is_empty());
+}
+
+#[test]
+fn new_resets_modifiers() {
+    let stat: TrackedStat = TrackedStat::new(Stat::new(10).with_bonus(5).with_max(20));
+    assert_eq!(stat.stat(), &Stat::new(10).with_max(20));
+}
+
+#[test]
+fn total() {
+    let mut stat: TrackedStat = Stat::new(10).into();
+    stat.add("sword", 5);
+    stat.multiply("haste", 2.0);
+    stat.apply("ring", Modifier::new(1, 0.5));
+
+    // (10 + 6) * 1 = 16
+    assert_eq!(stat.total(), 16);
+    assert_eq!(
+        stat.stat(),
+        &Stat::new(10).with_modifier(Modifier::new(6, 1.0))
+    );
+}
+
+#[test]
+fn contributions() {
+    let mut stat: TrackedStat = Stat::new(10).into();
+    stat.add("sword", 5);
+    stat -= 2;
+    stat *= 2.0;
+
+    assert_eq!(
+        stat.contributions(),
+        &[
+            Contribution {
+                source: Some("sword"),
+                modifier: Modifier::from_bonus(5),
+            },
+            Contribution {
+                source: None,
+                modifier: Modifier::from_bonus(-2),
+            },
+            Contribution {
+                source: None,
+                modifier: Modifier::from_multiplier(2.0),
+            },
+        ]
+    );
+}
+
+#[test]
+fn breakdown() {
+    let mut stat: TrackedStat<i32, u32> = Stat::new(10).into();
+    stat.add(1, 5);
+    stat.multiply(2, 2.0);
+
+    let breakdown: Vec<_> = stat
+        .breakdown()
+        .map(|(contribution, effect)| (contribution.source, effect))
+        .collect();
+
+    // Without the bonus: 10 * 2 = 20, so the bonus contributes 10.
+    // Without the multiplier: 10 + 5 = 15, so the multiplier contributes 15.
+    assert_eq!(breakdown, vec![(Some(1), 10.0), (Some(2), 15.0)]);
+}
+
+#[test]
+fn display_contribution() {
+    let contribution: Contribution = Contribution {
+        source: Some("sword"),
+        modifier: Modifier::from_bonus(5),
+    };
+    assert_eq!(contribution.to_string(), "sword: (+5) x 1");
+}
+
+#[test]
+fn subtract_unsigned() {
+    let mut stat: TrackedStat<u32> = Stat::new(10).into();
+    stat -= 4;
+    assert_eq!(stat.total(), 6);
+    assert_eq!(stat.contributions()[0].modifier, Modifier::from_bonus(-4));
+}
@@ -0,0 +1,95 @@
+//! Tests the various methods of `VectorStat`.
+
+use immediate_stats::*;
+
+#[test]
+fn reset() {
+    let mut stat = Vec3Stat::new([1.0, 2.0, 3.0]);
+    stat += 5.0;
+    stat *= [1.0, 2.0, 3.0];
+    stat.reset_modifiers();
+
+    assert_eq!(stat, Vec3Stat::new([1.0, 2.0, 3.0]));
+}
+
+#[test]
+fn splat() {
+    assert_eq!(Vec2Stat::splat(4.0), Vec2Stat::new([4.0, 4.0]));
+}
+
+#[test]
+fn scalar_operators() {
+    let mut stat = Vec3Stat::new([1.0, 2.0, 3.0]);
+    stat += 1.0;
+    stat *= 2.0;
+    assert_eq!(stat.total(), [4.0, 6.0, 8.0]);
+
+    stat -= 1.0;
+    stat /= 2.0;
+    assert_eq!(stat.total(), [1.0, 2.0, 3.0]);
+}
+
+#[test]
+fn component_wise_operators() {
+    let mut stat = Vec3Stat::splat(10);
+    stat += [1, 2, 3];
+    stat *= [1.0, 0.5, 2.0];
+    assert_eq!(stat.total(), [11, 6, 26]);
+
+    stat -= [1, 2, 3];
+    stat /= [1.0, 0.5, 2.0];
+    assert_eq!(stat.total(), [10, 10, 10]);
+}
+
+#[test]
+fn apply() {
+    let mut stat = Vec2Stat::new([10, 20]);
+    stat.apply(Modifier::new(5, 2.0));
+    assert_eq!(stat.total(), [30, 50]);
+
+    stat.apply_each([Modifier::from_bonus(5), Modifier::from_multiplier(0.5)]);
+    assert_eq!(stat.total(), [40, 25]);
+}
+
+#[test]
+fn apply_scaled() {
+    let mut stat = Vec2Stat::new([10, 20]);
+    stat.apply_scaled(Modifier::new(4, 3.0), 0.5);
+    assert_eq!(stat.total(), [24, 44]);
+}
+
+#[test]
+fn index() {
+    let mut stat = Vec3Stat::new([1.0, 2.0, 3.0]);
+    stat[1] += 2.0;
+    assert_eq!(stat[1].total(), 4.0);
+    assert_eq!(stat.base(), [1.0, 2.0, 3.0]);
+}
+
+#[test]
+fn display() {
+    let mut stat = Vec2Stat::new([1.0, 2.0]);
+    stat *= [2.0, 1.5];
+    assert_eq!(stat.to_string(), "[(1 + 0) x 2, (2 + 0) x 1.5]");
+}
+
+#[derive(StatContainer)]
+struct Movement {
+    speed: Vec3Stat,
+    gravity: Vec2Stat<f64>,
+}
+
+#[test]
+fn derive() {
+    let mut movement = Movement {
+        speed: Vec3Stat::splat(5.0),
+        gravity: Vec2Stat::new([0.0, -9.8]),
+    };
+
+    movement.speed *= [2.0, 1.0, 1.0];
+    movement.gravity *= 0.5;
+    movement.reset_modifiers();
+
+    assert_eq!(movement.speed.total(), [5.0; 3]);
+    assert_eq!(movement.gravity.total(), [0.0, -9.8]);
+}
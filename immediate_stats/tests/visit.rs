@@ -0,0 +1,187 @@
+//! Tests visiting the stats of a container by path.
+
+use immediate_stats::*;
+
+#[derive(StatContainer, Default)]
+struct Movement {
+    speed: Stat,
+    jump: LayeredStat<f32>,
+}
+
+#[derive(StatContainer, Default)]
+struct Player {
+    #[stat]
+    movement: Movement,
+    #[stat]
+    health: Pool,
+    #[derived(self.movement.speed.total() / 2)]
+    dodge: DerivedStat,
+    aim: VectorStat<f32, 2>,
+    #[stat_ignore]
+    ignored: Stat,
+}
+
+fn paths(container: &impl StatContainer) -> Vec<String> {
+    let mut paths = Vec::new();
+    container.for_each_stat(|path, _| paths.push(path.to_string()));
+    paths
+}
+
+#[test]
+fn visit_struct() {
+    assert_eq!(
+        paths(&Player::default()),
+        [
+            "movement.speed",
+            "movement.jump",
+            "health",
+            "dodge",
+            "aim.0",
+            "aim.1",
+        ]
+    );
+}
+
+#[test]
+fn visit_tuple_struct() {
+    #[derive(StatContainer)]
+    struct Speed(#[expect(dead_code)] u8, Stat);
+
+    assert_eq!(paths(&Speed(0, Stat::new(10))), ["1"]);
+}
+
+#[derive(StatContainer)]
+enum Enemy {
+    Walker {
+        speed: Stat,
+        #[expect(dead_code)]
+        name: &'static str,
+    },
+    Flyer(Stat<f32>, Stat<f32>, #[expect(dead_code)] &'static str),
+    Statue,
+}
+
+#[test]
+fn visit_enum() {
+    let walker = Enemy::Walker {
+        speed: Stat::new(10),
+        name: "Zombie",
+    };
+    assert_eq!(paths(&walker), ["speed"]);
+
+    let flyer = Enemy::Flyer(Stat::new(5.0), Stat::new(3.0), "Bat");
+    assert_eq!(paths(&flyer), ["0", "1"]);
+    assert!(paths(&Enemy::Statue).is_empty());
+}
+
+#[test]
+fn visit_map() {
+    let stats = StatMap::<String>::new(10)
+        .with_base("speed".into(), 5)
+        .with_base("luck".into(), 1);
+
+    assert_eq!(paths(&stats), [r#"["luck"]"#, r#"["speed"]"#]);
+
+    // Inserting a key doesn't change the paths of the other entries.
+    let stats = stats.with_base("agility".into(), 2);
+    assert_eq!(
+        paths(&stats),
+        [r#"["agility"]"#, r#"["luck"]"#, r#"["speed"]"#]
+    );
+}
+
+#[test]
+fn visit_downcast() {
+    let mut player = Player::default();
+    player.movement.speed = Stat::new(10);
+
+    let mut speed = None;
+    player.for_each_stat(|path, stat| {
+        if path == "movement.speed" {
+            speed = stat.downcast_ref::<Stat>().map(Stat::total);
+        } else if path == "movement.jump" {
+            assert!(stat.is::<LayeredStat<f32>>());
+            assert!(stat.downcast_ref::<Stat>().is_none());
+        }
+    });
+
+    assert_eq!(speed, Some(10));
+}
+
+#[test]
+fn visit_mut() {
+    let mut player = Player {
+        movement: Movement {
+            speed: Stat::new(10),
+            jump: LayeredStat::new(4.0),
+        },
+        health: Pool::new(100),
+        aim: VectorStat::new([2.0, 1.0]),
+        ignored: Stat::new(10),
+        ..Default::default()
+    };
+
+    // Slow everything by 50%.
+    player.for_each_stat_mut(|_, stat| stat.multiply(0.5));
+
+    assert_eq!(player.movement.speed.total(), 5);
+    assert_eq!(player.movement.jump.total(), 2.0);
+    assert_eq!(player.health.max.total(), 50);
+    assert_eq!(player.aim.total(), [1.0, 0.5]);
+    assert_eq!(player.ignored.total(), 10);
+
+    player.for_each_stat_mut(|path, stat| {
+        if path == "movement.speed" {
+            *stat.downcast_mut::<Stat>().unwrap() += 2;
+        }
+    });
+
+    assert_eq!(player.movement.speed.total(), 6);
+}
+
+#[test]
+fn visit_tracked() {
+    let mut tracked = TrackedStat::<i32>::from(Stat::new(10));
+
+    assert_eq!(paths(&tracked), [""]);
+
+    tracked.for_each_stat_mut(|_, stat| stat.multiply(2.0));
+    assert_eq!(tracked.total(), 20);
+    assert_eq!(
+        tracked.contributions(),
+        [Contribution {
+            source: None,
+            modifier: Modifier::from_multiplier(2.0),
+        }]
+    );
+
+    tracked.for_each_stat(|_, stat| assert!(stat.is::<TrackedStat>()));
+}
+
+#[test]
+fn visitor_path() {
+    let player = Player::default();
+    let mut containers = Vec::new();
+    let mut callback = |path: &str, _: &dyn AnyStat| containers.push(path.to_string());
+
+    let mut visitor = StatVisitor::new(&mut callback);
+    visitor.field("player", &player.movement);
+    assert_eq!(visitor.path(), "");
+    drop(visitor);
+
+    assert_eq!(containers, ["player.speed", "player.jump"]);
+}
+
+/// The derive should only need `StatContainer` to be in scope.
+mod minimal_imports {
+    use immediate_stats::{Stat, StatContainer};
+
+    #[derive(StatContainer)]
+    pub struct Speed(pub Stat);
+}
+
+#[test]
+fn visit_minimal_imports() {
+    let speed = minimal_imports::Speed(Stat::new(10));
+    assert_eq!(paths(&speed), ["0"]);
+}
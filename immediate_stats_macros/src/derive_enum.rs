@@ -1,14 +1,16 @@
 use crate::FieldOptions;
+use proc_macro_error::emit_error;
 use proc_macro2::{Span, TokenStream};
 use quote::quote;
-use syn::{DataEnum, Ident, Variant};
+use syn::spanned::Spanned;
+use syn::{DataEnum, Fields, Ident, Variant};
 
-/// Returns a match statement that can be used to reset an enum.
-pub fn reset_enum(body: &DataEnum) -> TokenStream {
+/// Returns a match statement that can be used to call `method` on an enum's stat fields.
+pub fn propagate_enum(body: &DataEnum, method: &Ident) -> TokenStream {
     body.variants
         .iter()
-        .map(|variant| {
-            let cases = reset_variant(&variant);
+        .flat_map(|variant| {
+            let cases = propagate_variant(variant, method);
             quote! {
                 match self {
                     #cases
@@ -16,58 +18,114 @@ pub fn reset_enum(body: &DataEnum) -> TokenStream {
                 }
             }
         })
-        .flatten()
         .collect()
 }
 
-/// Returns a case that can be used to reset the variant.
+/// Returns a match statement that can be used to update an enum's derived stats.
+/// Emits an error for any `#[derived(...)]` attributes, since they can't refer to other fields.
+pub fn update_enum(body: &DataEnum) -> TokenStream {
+    for field in body.variants.iter().flat_map(|variant| &variant.fields) {
+        if let Some(attribute) = FieldOptions::from_field(field).derived {
+            emit_error!(
+                attribute.span(),
+                "`derived` attribute is only supported on structs."
+            );
+        }
+    }
+
+    propagate_enum(body, &Ident::new("update_derived", Span::call_site()))
+}
+
+/// Returns a case that can be used to call `method` on the variant's stat fields.
 /// If there are no stat fields, the result will be empty.
-fn reset_variant(variant: &Variant) -> TokenStream {
-    // List of all identifiers that need to be reset.
-    let names: Vec<Ident> = variant
-        .fields
-        .iter()
-        .enumerate()
-        .filter_map(|(index, field)| {
-            let options = FieldOptions::from_field(field);
+fn propagate_variant(variant: &Variant, method: &Ident) -> TokenStream {
+    let Some(VariantPattern { pattern, fields }) = VariantPattern::from_variant(variant) else {
+        return TokenStream::new();
+    };
 
-            if options.is_stat() {
-                return Some(match options.ident {
-                    Some(ident) => ident,
-                    None => get_ident_from_index(index),
-                });
-            }
+    let names = fields.iter().map(|(binding, _)| binding);
 
-            None
+    quote! {
+        #pattern => {
+            #(#names.#method();)*
+        },
+    }
+}
+
+/// Returns a match statement that can be used to visit an enum's stat fields.
+///
+/// The same code works for both `visit_stats` and `visit_stats_mut`,
+/// since the fields are bound by reference.
+pub fn visit_enum(body: &DataEnum) -> TokenStream {
+    body.variants
+        .iter()
+        .filter_map(VariantPattern::from_variant)
+        .map(|VariantPattern { pattern, fields }| {
+            let (bindings, names): (Vec<_>, Vec<_>) = fields.into_iter().unzip();
+            quote! {
+                match self {
+                    #pattern => {
+                        #(visitor.field(#names, #bindings);)*
+                    },
+                    _ => {}
+                }
+            }
         })
-        .collect();
+        .collect()
+}
 
-    if names.is_empty() {
-        return TokenStream::new();
-    }
+/// A pattern that matches a variant and binds each of its stat fields.
+struct VariantPattern {
+    pattern: TokenStream,
+    /// The binding and name of each stat field, using the index as the name for tuple variants.
+    fields: Vec<(Ident, String)>,
+}
+
+impl VariantPattern {
+    /// Returns `None` if the variant has no stat fields.
+    fn from_variant(variant: &Variant) -> Option<Self> {
+        let mut fields = Vec::new();
+        // Non-stat fields of tuple variants are skipped using `_`, so that each binding matches its position.
+        let mut elements = Vec::new();
 
-    let is_named = variant.fields.iter().next().unwrap().ident.is_some();
-    let ident = &variant.ident;
+        for (index, field) in variant.fields.iter().enumerate() {
+            let options = FieldOptions::from_field(field);
+
+            if !options.is_stat() {
+                elements.push(quote! { _ });
+                continue;
+            }
+
+            let (binding, name) = match options.ident {
+                Some(ident) => (ident.clone(), ident.to_string()),
+                None => (get_ident_from_index(index), index.to_string()),
+            };
 
-    if is_named {
-        quote! {
-            Self::#ident { #(#names,)* .. } => {
-                #(#names.reset_modifiers();)*
-            },
+            elements.push(quote! { #binding });
+            fields.push((binding, name));
         }
-    } else {
-        quote! {
-            Self::#ident ( #(#names,)* .. ) => {
-                #(#names.reset_modifiers();)*
-            },
+
+        if fields.is_empty() {
+            return None;
         }
+
+        let ident = &variant.ident;
+        let pattern = match &variant.fields {
+            Fields::Named(_) => {
+                let bindings = fields.iter().map(|(binding, _)| binding);
+                quote! { Self::#ident { #(#bindings,)* .. } }
+            }
+            _ => quote! { Self::#ident ( #(#elements,)* .. ) },
+        };
+
+        Some(Self { pattern, fields })
     }
 }
 
 /// Generates an alphabetic identifier from an index.
 fn get_ident_from_index(index: usize) -> Ident {
     Ident::new(
-        format!("{}", ('a' as u8 + index as u8) as char).as_str(),
+        format!("{}", (b'a' + index as u8) as char).as_str(),
         Span::call_site(),
     )
 }
@@ -1,35 +1,199 @@
 use crate::FieldOptions;
-use proc_macro2::TokenStream;
-use quote::quote;
-use syn::{DataStruct, Field, Index};
+use proc_macro_error::emit_error;
+use proc_macro2::{Span, TokenStream, TokenTree};
+use quote::{ToTokens, quote, quote_spanned};
+use syn::spanned::Spanned;
+use syn::{DataStruct, Expr, Field, Ident, Index, Member};
 
-/// Returns the code that can be used to reset a struct's stat fields.
-pub fn reset_struct(body: &DataStruct) -> TokenStream {
+/// Returns the code that can be used to call `method` on each of a struct's stat fields.
+pub fn propagate_struct(body: &DataStruct, method: &Ident) -> TokenStream {
     body.fields
         .iter()
         .enumerate()
-        .map(|(index, field)| reset_struct_field(field, index))
-        .flatten()
+        .flat_map(|(index, field)| propagate_struct_field(field, index, method))
         .collect()
 }
 
-/// Returns the method call that can be used to reset a stat field.
+/// Returns the method call that can be used to propagate `method` to a stat field.
 /// If the field is not a stat, the result will be empty.
 /// The `index`  is used for tuple/unnamed fields.
-fn reset_struct_field(field: &Field, index: usize) -> TokenStream {
-    let options = FieldOptions::from_field(&field);
+fn propagate_struct_field(field: &Field, index: usize, method: &Ident) -> TokenStream {
+    let options = FieldOptions::from_field(field);
 
     if options.is_stat() {
-        return match options.ident {
-            Some(ident) => {
-                quote! { self.#ident.reset_modifiers(); }
+        let member = get_member(field, index);
+        return quote! { self.#member.#method(); };
+    }
+
+    TokenStream::new()
+}
+
+/// Returns the code that can be used to visit each of a struct's stat fields,
+/// using `&mut` instead of `&` if `mutable` is true.
+pub fn visit_struct(body: &DataStruct, mutable: bool) -> TokenStream {
+    let reference = if mutable {
+        quote! { &mut }
+    } else {
+        quote! { & }
+    };
+
+    body.fields
+        .iter()
+        .enumerate()
+        .filter(|(_, field)| FieldOptions::from_field(field).is_stat())
+        .map(|(index, field)| {
+            let member = get_member(field, index);
+            let name = member_name(&member);
+            quote! { visitor.field(#name, #reference self.#member); }
+        })
+        .collect()
+}
+
+/// A field with the `#[derived(...)]` attribute.
+struct DerivedField {
+    member: Member,
+    expr: Expr,
+    /// The names of all fields of `self` that the expression uses.
+    dependencies: Vec<String>,
+}
+
+/// Returns the code that can be used to update a struct's derived stats.
+///
+/// Sub-containers are updated first, followed by each derived field,
+/// ordered so that any derived fields they depend on are updated before them.
+pub fn update_struct(body: &DataStruct) -> TokenStream {
+    let update = Ident::new("update_derived", Span::call_site());
+    let mut tokens = propagate_struct(body, &update);
+
+    let fields: Vec<DerivedField> = body
+        .fields
+        .iter()
+        .enumerate()
+        .filter_map(|(index, field)| {
+            let attribute = FieldOptions::from_field(field).derived?;
+
+            let expr = match attribute.parse_args::<Expr>() {
+                Ok(expr) => expr,
+                Err(error) => {
+                    emit_error!(
+                        attribute.span(),
+                        "`derived` attribute expects an expression, such as `#[derived(self.strength.total() * 2)]`: {}",
+                        error
+                    );
+                    return None;
+                }
+            };
+
+            let mut dependencies = Vec::new();
+            find_dependencies(expr.to_token_stream(), &mut dependencies);
+
+            Some(DerivedField {
+                member: get_member(field, index),
+                expr,
+                dependencies,
+            })
+        })
+        .collect();
+
+    for field in sort_derived(&fields) {
+        let member = &field.member;
+        let expr = &field.expr;
+
+        tokens.extend(quote_spanned! {expr.span()=>
+            let base = #expr;
+            self.#member.derive_from(base);
+        });
+    }
+
+    tokens
+}
+
+/// Orders derived fields so that each one comes after the derived fields it depends on.
+/// Emits an error if the dependencies contain a cycle.
+fn sort_derived(fields: &[DerivedField]) -> Vec<&DerivedField> {
+    /// The progress of a depth first search through a field.
+    #[derive(Copy, Clone, PartialEq)]
+    enum Visit {
+        Unvisited,
+        InProgress,
+        Done,
+    }
+
+    fn visit<'a>(
+        index: usize,
+        fields: &'a [DerivedField],
+        visits: &mut [Visit],
+        sorted: &mut Vec<&'a DerivedField>,
+    ) {
+        match visits[index] {
+            Visit::Done => return,
+            Visit::InProgress => {
+                let member = &fields[index].member;
+                emit_error!(
+                    member.span(),
+                    "derived stat `{}` depends on itself.",
+                    member_name(member)
+                );
+                return;
             }
-            None => {
-                let index = Index::from(index);
-                quote! { self.#index.reset_modifiers(); }
+            Visit::Unvisited => visits[index] = Visit::InProgress,
+        }
+
+        for dependency in &fields[index].dependencies {
+            if let Some(other) = fields
+                .iter()
+                .position(|field| &member_name(&field.member) == dependency)
+            {
+                visit(other, fields, visits, sorted);
             }
-        };
+        }
+
+        visits[index] = Visit::Done;
+        sorted.push(&fields[index]);
     }
 
-    TokenStream::new()
+    let mut visits = vec![Visit::Unvisited; fields.len()];
+    let mut sorted = Vec::with_capacity(fields.len());
+
+    for index in 0..fields.len() {
+        visit(index, fields, &mut visits, &mut sorted);
+    }
+
+    sorted
+}
+
+/// Finds all fields accessed using `self.field`, including inside of nested groups.
+fn find_dependencies(tokens: TokenStream, dependencies: &mut Vec<String>) {
+    let tokens: Vec<TokenTree> = tokens.into_iter().collect();
+
+    for (index, token) in tokens.iter().enumerate() {
+        match token {
+            TokenTree::Group(group) => find_dependencies(group.stream(), dependencies),
+            TokenTree::Ident(ident) if ident == "self" => {
+                if let (Some(TokenTree::Punct(dot)), Some(field)) =
+                    (tokens.get(index + 1), tokens.get(index + 2))
+                    && dot.as_char() == '.'
+                {
+                    dependencies.push(field.to_string());
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Returns the member used to access a field, using `index` for tuple/unnamed fields.
+fn get_member(field: &Field, index: usize) -> Member {
+    match &field.ident {
+        Some(ident) => Member::Named(ident.clone()),
+        None => Member::Unnamed(Index::from(index)),
+    }
+}
+
+/// Returns the name of a member, as it would be written after `self.`.
+fn member_name(member: &Member) -> String {
+    match member {
+        Member::Named(ident) => ident.to_string(),
+        Member::Unnamed(index) => index.index.to_string(),
+    }
 }
@@ -2,30 +2,70 @@ mod derive_enum;
 mod derive_struct;
 
 use proc_macro_error::{emit_call_site_error, emit_warning, proc_macro_error};
+use proc_macro2::{Span, TokenStream};
 use quote::{ToTokens, quote};
 use syn::spanned::Spanned;
-use syn::{Data, DeriveInput, Field, Ident, parse_macro_input};
+use syn::{Attribute, Data, DeriveInput, Field, Ident, parse_macro_input};
 
-#[proc_macro_derive(StatContainer, attributes(stat, stat_ignore, add_component))]
+#[proc_macro_derive(StatContainer, attributes(stat, stat_ignore, derived, add_component))]
 #[proc_macro_error]
 pub fn stat_container_derive(item: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let tree: DeriveInput = parse_macro_input!(item as DeriveInput);
     let ident = &tree.ident;
 
-    let method_contents = match tree.data.clone() {
-        Data::Struct(s) => derive_struct::reset_struct(&s),
-        Data::Enum(e) => derive_enum::reset_enum(&e),
-        Data::Union(_) => {
-            emit_call_site_error!("This trait cannot be derived from unions.");
-            return proc_macro::TokenStream::new();
+    let reset = Ident::new("reset_modifiers", Span::call_site());
+
+    let (reset_contents, update_contents, visit_contents, visit_mut_contents) =
+        match tree.data.clone() {
+            Data::Struct(s) => (
+                derive_struct::propagate_struct(&s, &reset),
+                derive_struct::update_struct(&s),
+                derive_struct::visit_struct(&s, false),
+                derive_struct::visit_struct(&s, true),
+            ),
+            Data::Enum(e) => {
+                let visit = derive_enum::visit_enum(&e);
+                (
+                    derive_enum::propagate_enum(&e, &reset),
+                    derive_enum::update_enum(&e),
+                    visit.clone(),
+                    visit,
+                )
+            }
+            Data::Union(_) => {
+                emit_call_site_error!("This trait cannot be derived from unions.");
+                return proc_macro::TokenStream::new();
+            }
+        };
+
+    let (impl_generics, type_generics, where_clause) = tree.generics.split_for_impl();
+
+    // Containers without stats use the default implementations, which avoids an unused `visitor`.
+    let visit_methods = if visit_contents.is_empty() {
+        TokenStream::new()
+    } else {
+        quote! {
+            fn visit_stats(&self, visitor: &mut ::immediate_stats::StatVisitor<'_>) {
+                #visit_contents
+            }
+
+            fn visit_stats_mut(&mut self, visitor: &mut ::immediate_stats::StatVisitorMut<'_>) {
+                #visit_mut_contents
+            }
         }
     };
 
     let trait_impl = quote! {
-        impl StatContainer for #ident {
+        impl #impl_generics StatContainer for #ident #type_generics #where_clause {
             fn reset_modifiers(&mut self) {
-                #method_contents
+                #reset_contents
             }
+
+            fn update_derived(&mut self) {
+                #update_contents
+            }
+
+            #visit_methods
         }
     };
 
@@ -36,12 +76,14 @@ pub fn stat_container_derive(item: proc_macro::TokenStream) -> proc_macro::Token
 #[derive(Default)]
 struct FieldOptions {
     ident: Option<Ident>,
-    /// True if the field's type contains the word "Stat".
+    /// True if the field's type contains the word "Stat", such as `Stat` or `Stat<f32>`.
     stat_type: bool,
     /// True if the field has the `#[stat]` attribute.
     include: bool,
     /// True if the field has the `#[stat_ignore]` attribute.
     exclude: bool,
+    /// The `#[derived(...)]` attribute, if the field has one.
+    derived: Option<Attribute>,
 }
 
 impl FieldOptions {
@@ -55,7 +97,7 @@ impl FieldOptions {
             );
         }
 
-        (self.include || self.stat_type) && !self.exclude
+        (self.include || self.stat_type || self.derived.is_some()) && !self.exclude
     }
 
     fn from_field(field: &Field) -> Self {
@@ -72,6 +114,7 @@ impl FieldOptions {
                     // Todo Warn about double tags.
                     "stat" => options.include = true,
                     "stat_ignore" => options.exclude = true,
+                    "derived" => options.derived = Some(attribute.clone()),
                     _ => continue,
                 }
             }